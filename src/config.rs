@@ -0,0 +1,289 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use indexmap::IndexMap;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::error::ConversionError;
+use crate::models::openapi::*;
+use crate::utils::sanitize::SanitizeConfig;
+
+/// Name of the project configuration file looked up in the working directory.
+pub const CONFIG_FILE: &str = "postman2openapi.toml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    #[default]
+    Yaml,
+    Json,
+    /// Readable documentation instead of a spec
+    Markdown,
+    Html,
+}
+
+impl OutputFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            OutputFormat::Yaml => "yaml",
+            OutputFormat::Json => "json",
+            OutputFormat::Markdown => "md",
+            OutputFormat::Html => "html",
+        }
+    }
+}
+
+/// How operations are grouped into tags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TagStrategy {
+    /// Every enclosing folder name
+    #[default]
+    Folders,
+    /// Only the outermost folder name
+    TopFolder,
+    /// The first literal segment of the path
+    Path,
+    /// No tags
+    None,
+}
+
+/// Values that replace what the converter derives for the `info` object.
+/// `external_docs` is written at the document root, where OpenAPI keeps it.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct InfoOverrides {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub version: Option<String>,
+    #[serde(alias = "termsOfService")]
+    pub terms_of_service: Option<String>,
+    pub contact: Option<Contact>,
+    pub license: Option<License>,
+    #[serde(alias = "externalDocs")]
+    pub external_docs: Option<ExternalDocs>,
+    /// `x-` extensions added to `info`
+    pub extensions: IndexMap<String, Value>,
+}
+
+impl InfoOverrides {
+    /// Checks that every extension name starts with `x-`.
+    pub fn check_extensions(&self) -> Result<(), String> {
+        match self.extensions.keys().find(|key| !key.starts_with("x-")) {
+            Some(key) => Err(format!("info extension '{}' must start with 'x-'", key)),
+            None => Ok(()),
+        }
+    }
+
+    pub fn apply(&self, spec: &mut OpenAPISpec) {
+        let info = &mut spec.info;
+        if let Some(title) = &self.title {
+            info.title = title.clone();
+        }
+        if let Some(description) = &self.description {
+            info.description = description.clone();
+        }
+        if let Some(version) = &self.version {
+            info.version = version.clone();
+        }
+        if let Some(terms) = &self.terms_of_service {
+            info.terms_of_service = Some(terms.clone());
+        }
+        if let Some(contact) = &self.contact {
+            // Fields are merged so a later source can change just the email
+            let current = info.contact.get_or_insert(Contact { name: None, url: None, email: None });
+            current.name = contact.name.clone().or(current.name.take());
+            current.url = contact.url.clone().or(current.url.take());
+            current.email = contact.email.clone().or(current.email.take());
+        }
+        if let Some(license) = &self.license {
+            info.license = Some(license.clone());
+        }
+        for (key, value) in &self.extensions {
+            info.extensions.insert(key.clone(), value.clone());
+        }
+        if let Some(external_docs) = &self.external_docs {
+            spec.external_docs = Some(external_docs.clone());
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RedactConfig {
+    pub enabled: bool,
+    /// Extra regular expressions whose matches are redacted
+    pub patterns: Vec<String>,
+}
+
+impl Default for RedactConfig {
+    fn default() -> Self {
+        RedactConfig { enabled: true, patterns: Vec::new() }
+    }
+}
+
+/// Settings for one input, keyed in `[collections]` by its file name
+/// (or file stem).
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CollectionConfig {
+    /// Output filename instead of `<stem>_openapi.<format>`
+    pub output: Option<String>,
+    pub info: InfoOverrides,
+    /// Replaces the project-wide servers
+    pub servers: Option<Vec<Server>>,
+    pub tags: Option<TagStrategy>,
+    /// Added to the project-wide deny-list
+    pub ignore_headers: Vec<String>,
+}
+
+/// Project settings read from `postman2openapi.toml`. Command-line flags
+/// take precedence over everything set here.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectConfig {
+    pub input_dir: Option<String>,
+    pub output_dir: Option<String>,
+    /// Also convert inputs in subdirectories of the input directory
+    pub recursive: bool,
+    /// Globs an input's path relative to the input directory must match
+    pub include: Vec<String>,
+    /// Globs excluding inputs, matched like `include`
+    pub exclude: Vec<String>,
+    /// OpenAPI version written to generated specs
+    pub openapi: Option<String>,
+    pub format: Option<OutputFormat>,
+    pub info: InfoOverrides,
+    pub servers: Vec<Server>,
+    pub tags: TagStrategy,
+    /// Header parameters left out of generated specs, matched
+    /// case-insensitively; a trailing `*` matches any suffix
+    pub ignore_headers: Vec<String>,
+    pub redact: RedactConfig,
+    /// Keep pre-request and test scripts as `x-postman-*` extensions
+    pub keep_scripts: bool,
+    pub sanitize: Option<SanitizeConfig>,
+    pub collections: HashMap<String, CollectionConfig>,
+}
+
+/// Returns the config file in `dir`, if there is one.
+pub fn find_config(dir: &Path) -> Option<PathBuf> {
+    let path = dir.join(CONFIG_FILE);
+    path.is_file().then_some(path)
+}
+
+impl ProjectConfig {
+    pub fn load(config_file: &Path) -> Result<Self, ConversionError> {
+        let contents = fs::read_to_string(config_file)
+            .map_err(ConversionError::FileError)?;
+        let config: ProjectConfig = toml::from_str(&contents)
+            .map_err(|e| ConversionError::InvalidFormat(format!("{}: {}", config_file.display(), e)))?;
+
+        let invalid = |message: String| ConversionError::InvalidFormat(format!("{}: {}", config_file.display(), message));
+        config.info.check_extensions().map_err(invalid)?;
+        for collection in config.collections.values() {
+            collection.info.check_extensions().map_err(invalid)?;
+        }
+
+        if let Some(version) = &config.openapi {
+            if !version.starts_with("3.0.") && !version.starts_with("3.1.") {
+                return Err(ConversionError::InvalidFormat(format!(
+                    "{}: unsupported OpenAPI version '{}'", config_file.display(), version
+                )));
+            }
+        }
+        Ok(config)
+    }
+
+    /// Finds the `[collections]` entry for an input file.
+    pub fn collection(&self, input: &Path) -> Option<&CollectionConfig> {
+        let file_name = input.file_name().and_then(|s| s.to_str())?;
+        self.collections.get(file_name).or_else(|| {
+            let stem = input.file_stem().and_then(|s| s.to_str())?;
+            self.collections.get(stem)
+        })
+    }
+
+    /// Applies the document-level settings: OpenAPI version, info and servers.
+    pub fn apply_document(&self, spec: &mut OpenAPISpec) {
+        if let Some(version) = &self.openapi {
+            spec.openapi = version.clone();
+        }
+        self.info.apply(spec);
+        if !self.servers.is_empty() {
+            spec.servers = self.servers.clone();
+        }
+    }
+
+    /// Applies every setting to a spec converted from one input, with the
+    /// input's `[collections]` entry on top of the project-wide values.
+    pub fn apply(&self, spec: &mut OpenAPISpec, collection: Option<&CollectionConfig>) {
+        self.apply_document(spec);
+
+        let mut tags = self.tags;
+        let mut ignore_headers = self.ignore_headers.clone();
+        if let Some(collection) = collection {
+            collection.info.apply(spec);
+            if let Some(servers) = &collection.servers {
+                spec.servers = servers.clone();
+            }
+            tags = collection.tags.unwrap_or(tags);
+            ignore_headers.extend(collection.ignore_headers.iter().cloned());
+        }
+
+        apply_tag_strategy(spec, tags);
+        remove_headers(spec, &ignore_headers);
+    }
+}
+
+fn apply_tag_strategy(spec: &mut OpenAPISpec, strategy: TagStrategy) {
+    if strategy == TagStrategy::Folders {
+        return;
+    }
+
+    let mut tags_set = BTreeSet::new();
+    for (path, operations) in spec.paths.iter_mut() {
+        for operation in operations.values_mut() {
+            match strategy {
+                TagStrategy::Folders => {}
+                TagStrategy::TopFolder => operation.tags.truncate(1),
+                TagStrategy::Path => {
+                    operation.tags = path.split('/')
+                        .find(|segment| !segment.is_empty() && !segment.starts_with('{'))
+                        .map(|segment| vec![segment.to_string()])
+                        .unwrap_or_default();
+                }
+                TagStrategy::None => operation.tags.clear(),
+            }
+            tags_set.extend(operation.tags.iter().cloned());
+        }
+    }
+
+    spec.tags = tags_set.into_iter()
+        .map(|tag| Tag { name: tag })
+        .collect();
+}
+
+fn header_matches(pattern: &str, name: &str) -> bool {
+    let name = name.to_lowercase();
+    let pattern = pattern.to_lowercase();
+    match pattern.strip_suffix('*') {
+        Some(prefix) => name.starts_with(prefix),
+        None => name == pattern,
+    }
+}
+
+fn remove_headers(spec: &mut OpenAPISpec, ignore_headers: &[String]) {
+    if ignore_headers.is_empty() {
+        return;
+    }
+    for operations in spec.paths.values_mut() {
+        for operation in operations.values_mut() {
+            operation.parameters.retain(|parameter| {
+                parameter.param_in != "header"
+                    || !ignore_headers.iter().any(|pattern| header_matches(pattern, &parameter.name))
+            });
+        }
+    }
+}
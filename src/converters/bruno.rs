@@ -0,0 +1,220 @@
+use crate::models::bruno::*;
+use crate::models::openapi::OpenAPISpec;
+use crate::models::postman::{self, FormParameter, Item, PostmanCollection};
+use crate::converters::curl::base64_encode;
+use crate::converters::postman::convert_postman_to_openapi;
+use crate::utils::path::{parse_query_string, split_url};
+use serde_json::Value;
+
+fn form_parameters(entries: &[BruEntry]) -> Vec<FormParameter> {
+    entries.iter()
+        .map(|entry| {
+            // Multipart file fields are written as `@file(path)`
+            let file = entry.value.strip_prefix("@file(").and_then(|v| v.strip_suffix(')'));
+            FormParameter {
+                key: Some(entry.key.clone()),
+                value: Some(file.unwrap_or(&entry.value).to_string()),
+                param_type: Some(if file.is_some() { "file" } else { "text" }.to_string()),
+                description: None,
+                disabled: Some(entry.disabled),
+            }
+        })
+        .collect()
+}
+
+fn raw_body(raw: &str) -> postman::Body {
+    postman::Body {
+        mode: Some("raw".to_string()),
+        raw: Some(raw.to_string()),
+        urlencoded: None,
+        formdata: None,
+    }
+}
+
+/// Translates the `auth:*` block selected by the verb block's `auth` key
+/// into the header or query parameter the request would actually send.
+fn apply_auth(bru: &BruFile, method: &str, headers: &mut Vec<postman::Header>, queries: &mut Vec<postman::Query>) {
+    match bru.value(method, "auth") {
+        Some("bearer") => {
+            let token = bru.value("auth:bearer", "token").unwrap_or("");
+            headers.push(postman::Header::new("Authorization", &format!("Bearer {}", token)));
+        }
+        Some("basic") => {
+            let credentials = format!(
+                "{}:{}",
+                bru.value("auth:basic", "username").unwrap_or(""),
+                bru.value("auth:basic", "password").unwrap_or("")
+            );
+            // Variables can't be encoded before their values are known
+            let value = if credentials.contains("{{") {
+                credentials
+            } else {
+                base64_encode(credentials.as_bytes())
+            };
+            headers.push(postman::Header::new("Authorization", &format!("Basic {}", value)));
+        }
+        Some("apikey") => {
+            let key = bru.value("auth:apikey", "key").unwrap_or("X-API-Key");
+            let value = bru.value("auth:apikey", "value").unwrap_or("");
+            if bru.value("auth:apikey", "placement") == Some("queryparams") {
+                queries.push(postman::Query {
+                    key: Some(key.to_string()),
+                    value: Some(value.to_string()),
+                    description: None,
+                    disabled: None,
+                });
+            } else {
+                headers.push(postman::Header::new(key, value));
+            }
+        }
+        _ => {}
+    }
+}
+
+fn build_request(bru: &BruFile, method: &str) -> postman::Request {
+    let url = bru.value(method, "url").unwrap_or("");
+    let split = split_url(url);
+
+    let path = split.path.split('/')
+        .filter(|s| !s.is_empty())
+        .map(|s| Value::String(s.to_string()))
+        .collect();
+
+    let mut variables: Vec<postman::Variable> = bru.dictionary("params:path").iter()
+        .map(|entry| postman::Variable {
+            key: Some(entry.key.clone()),
+            value: Some(entry.value.clone()),
+        })
+        .collect();
+    // Every `:name` segment is a path parameter, listed in `params:path` or not
+    for name in split.path.split('/').filter_map(|s| s.strip_prefix(':')) {
+        if !variables.iter().any(|v| v.key.as_deref() == Some(name)) {
+            variables.push(postman::Variable { key: Some(name.to_string()), value: None });
+        }
+    }
+
+    // Older files only carry the query in the URL, newer ones also list it
+    // (with disabled entries) in `params:query`.
+    let query_entries = match bru.dictionary("params:query") {
+        [] => bru.dictionary("query"),
+        entries => entries,
+    };
+    let mut queries: Vec<postman::Query> = if query_entries.is_empty() {
+        split.query
+            .map(parse_query_string)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(key, value)| postman::Query {
+                key: Some(key),
+                value: Some(value),
+                description: None,
+                disabled: None,
+            })
+            .collect()
+    } else {
+        query_entries.iter()
+            .map(|entry| postman::Query {
+                key: Some(entry.key.clone()),
+                value: Some(entry.value.clone()),
+                description: None,
+                disabled: Some(entry.disabled),
+            })
+            .collect()
+    };
+
+    let mut headers: Vec<postman::Header> = bru.dictionary("headers").iter()
+        .map(|entry| postman::Header {
+            key: Some(entry.key.clone()),
+            value: Some(entry.value.clone()),
+            description: None,
+            disabled: Some(entry.disabled),
+        })
+        .collect();
+
+    apply_auth(bru, method, &mut headers, &mut queries);
+
+    let (body, content_type) = match bru.value(method, "body") {
+        Some("json") => (bru.text("body:json").map(raw_body), Some("application/json")),
+        Some("text") => (bru.text("body:text").map(raw_body), Some("text/plain")),
+        Some("xml") => (bru.text("body:xml").map(raw_body), Some("application/xml")),
+        Some("formUrlEncoded") => (
+            Some(postman::Body {
+                mode: Some("urlencoded".to_string()),
+                raw: None,
+                urlencoded: Some(form_parameters(bru.dictionary("body:form-urlencoded"))),
+                formdata: None,
+            }),
+            None,
+        ),
+        Some("multipartForm") => (
+            Some(postman::Body {
+                mode: Some("formdata".to_string()),
+                raw: None,
+                urlencoded: None,
+                formdata: Some(form_parameters(bru.dictionary("body:multipart-form"))),
+            }),
+            None,
+        ),
+        _ => (None, None),
+    };
+
+    let has_content_type = headers.iter().any(|h| h.is("content-type"));
+    if let (Some(content_type), false) = (content_type, has_content_type) {
+        headers.push(postman::Header::new("Content-Type", content_type));
+    }
+
+    postman::Request {
+        method: Some(method.to_uppercase()),
+        url: Some(postman::Url {
+            path: Some(path),
+            variable: Some(variables),
+            query: Some(queries),
+        }),
+        header: Some(headers),
+        body,
+        description: bru.text("docs").map(|docs| docs.to_string()),
+    }
+}
+
+fn folder_items(folder: &BrunoFolder) -> Vec<Item> {
+    let mut items: Vec<Item> = folder.requests.iter()
+        .filter_map(|bru| {
+            let method = bru.method()?;
+            Some(Item {
+                name: bru.name().map(|name| name.to_string()),
+                item: None,
+                request: Some(build_request(bru, method)),
+                response: None,
+                event: None,
+            })
+        })
+        .collect();
+
+    items.extend(folder.folders.iter().map(|sub| Item {
+        name: Some(sub.name.clone()),
+        item: Some(folder_items(sub)),
+        request: None,
+        response: None,
+        event: None,
+    }));
+
+    items
+}
+
+/// Maps a Bruno collection onto the Postman model: folders become folder
+/// items (and therefore tags) and each request file becomes a request item.
+pub fn convert_bruno_to_postman(collection: &BrunoCollection) -> PostmanCollection {
+    PostmanCollection {
+        info: Some(postman::Info {
+            name: Some(collection.name.clone()),
+            description: None,
+            version: None,
+        }),
+        item: Some(folder_items(&collection.root)),
+        event: None,
+    }
+}
+
+pub fn convert_bruno_to_openapi(collection: &BrunoCollection) -> OpenAPISpec {
+    convert_postman_to_openapi(&convert_bruno_to_postman(collection))
+}
\ No newline at end of file
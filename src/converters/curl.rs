@@ -0,0 +1,223 @@
+use crate::models::curl::{split_urlencode, CurlCommand};
+use crate::models::openapi::OpenAPISpec;
+use crate::models::postman::{self, FormParameter, Item, PostmanCollection};
+use crate::converters::har::merge_request;
+use crate::converters::postman::convert_postman_to_openapi;
+use crate::utils::path::{collapse_path_template, parse_query_string, split_url};
+use indexmap::IndexMap;
+use serde_json::Value;
+
+pub(crate) fn base64_encode(input: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::new();
+
+    for chunk in input.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+fn query(key: String, value: String) -> postman::Query {
+    postman::Query {
+        key: Some(key),
+        value: Some(value),
+        description: None,
+        disabled: None,
+    }
+}
+
+fn form_parameters(pairs: Vec<(String, String)>) -> Vec<FormParameter> {
+    pairs.into_iter()
+        .map(|(key, value)| FormParameter {
+            key: Some(key),
+            value: Some(value),
+            param_type: Some("text".to_string()),
+            description: None,
+            disabled: None,
+        })
+        .collect()
+}
+
+fn form_body(mode: &str, fields: Vec<FormParameter>) -> postman::Body {
+    let (urlencoded, formdata) = if mode == "formdata" {
+        (None, Some(fields))
+    } else {
+        (Some(fields), None)
+    };
+    postman::Body {
+        mode: Some(mode.to_string()),
+        raw: None,
+        urlencoded,
+        formdata,
+    }
+}
+
+fn build_request(command: &CurlCommand, segments: &[String], variables: &[(String, String)], url_query: Option<&str>) -> postman::Request {
+    let mut headers: Vec<postman::Header> = command.headers.iter()
+        .map(|(key, value)| postman::Header::new(key, value))
+        .collect();
+
+    if let Some(user) = &command.user {
+        if !command.has_header("authorization") {
+            headers.push(postman::Header::new("Authorization", &format!("Basic {}", base64_encode(user.as_bytes()))));
+        }
+    }
+
+    let mut queries: Vec<postman::Query> = url_query
+        .map(parse_query_string)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(key, value)| query(key, value))
+        .collect();
+    for argument in &command.url_query {
+        let pairs = match argument.strip_prefix('+') {
+            Some(encoded) => parse_query_string(encoded),
+            None => vec![split_urlencode(argument)],
+        };
+        queries.extend(pairs.into_iter().map(|(key, value)| query(key, value)));
+    }
+
+    let data = command.data.join("&");
+    let body = if command.get {
+        // -G turns every payload into query parameters
+        queries.extend(parse_query_string(&data).into_iter().map(|(k, v)| query(k, v)));
+        queries.extend(command.urlencoded.iter().cloned().map(|(k, v)| query(k, v)));
+        None
+    } else if !command.form.is_empty() {
+        let fields = command.form.iter()
+            .map(|(key, value, is_file)| FormParameter {
+                key: Some(key.clone()),
+                value: Some(if *is_file { value[1..].to_string() } else { value.clone() }),
+                param_type: Some(if *is_file { "file" } else { "text" }.to_string()),
+                description: None,
+                disabled: None,
+            })
+            .collect();
+        Some(form_body("formdata", fields))
+    } else if !command.urlencoded.is_empty() {
+        let mut pairs = parse_query_string(&data);
+        pairs.extend(command.urlencoded.iter().cloned());
+        Some(form_body("urlencoded", form_parameters(pairs)))
+    } else if !command.data.is_empty() {
+        if command.has_header("content-type") {
+            Some(postman::Body {
+                mode: Some("raw".to_string()),
+                raw: Some(data),
+                urlencoded: None,
+                formdata: None,
+            })
+        } else if serde_json::from_str::<Value>(&data).map(|v| v.is_object() || v.is_array()).unwrap_or(false) {
+            // curl would send this as a form, but a JSON payload without a
+            // Content-Type header is almost always a forgotten `-H`.
+            headers.push(postman::Header::new("Content-Type", "application/json"));
+            Some(postman::Body {
+                mode: Some("raw".to_string()),
+                raw: Some(data),
+                urlencoded: None,
+                formdata: None,
+            })
+        } else {
+            Some(form_body("urlencoded", form_parameters(parse_query_string(&data))))
+        }
+    } else {
+        None
+    };
+
+    // Postman reads the body content type from the request headers
+    if let Some(content_type) = headers.iter_mut().find(|h| h.is("content-type")) {
+        if let Some(value) = &content_type.value {
+            content_type.value = Some(value.split(';').next().unwrap_or("").trim().to_lowercase());
+        }
+    }
+
+    postman::Request {
+        method: Some(command.effective_method()),
+        url: Some(postman::Url {
+            path: Some(segments.iter().map(|s| Value::String(s.clone())).collect()),
+            variable: Some(variables.iter()
+                .map(|(key, value)| postman::Variable {
+                    key: Some(key.clone()),
+                    value: Some(value.clone()),
+                })
+                .collect()),
+            query: Some(queries),
+        }),
+        header: Some(headers),
+        body,
+        description: None,
+    }
+}
+
+/// Builds a Postman collection from a list of curl invocations, grouping
+/// them into one folder per host. Commands hitting the same method and
+/// templated path are merged into a single request.
+pub fn convert_curl_to_postman(commands: &[CurlCommand]) -> PostmanCollection {
+    let mut hosts: IndexMap<String, IndexMap<(String, String), Item>> = IndexMap::new();
+
+    for command in commands {
+        let split = split_url(&command.url);
+        let (segments, variables) = collapse_path_template(split.path);
+        let method = command.effective_method();
+        let key = (method.clone(), segments.join("/"));
+
+        let request = build_request(command, &segments, &variables, split.query);
+
+        let items = hosts.entry(split.host.to_string()).or_default();
+        match items.get_mut(&key) {
+            Some(item) => {
+                if let Some(existing) = item.request.as_mut() {
+                    merge_request(existing, request);
+                }
+            }
+            None => {
+                let display_path: Vec<String> = segments.iter()
+                    .map(|s| match s.strip_prefix(':') {
+                        Some(name) => format!("{{{}}}", name),
+                        None => s.clone(),
+                    })
+                    .collect();
+                let item = Item {
+                    name: Some(format!("{} /{}", method, display_path.join("/"))),
+                    item: None,
+                    request: Some(request),
+                    response: None,
+                    event: None,
+                };
+                items.insert(key, item);
+            }
+        }
+    }
+
+    let folders = hosts.into_iter()
+        .map(|(host, items)| Item {
+            name: Some(host),
+            item: Some(items.into_values().collect()),
+            request: None,
+            response: None,
+            event: None,
+        })
+        .collect();
+
+    PostmanCollection {
+        info: Some(postman::Info {
+            name: Some("curl Import".to_string()),
+            description: Some("Generated from curl commands".to_string()),
+            version: None,
+        }),
+        item: Some(folders),
+        event: None,
+    }
+}
+
+pub fn convert_curl_to_openapi(commands: &[CurlCommand]) -> OpenAPISpec {
+    convert_postman_to_openapi(&convert_curl_to_postman(commands))
+}
\ No newline at end of file
@@ -0,0 +1,272 @@
+use crate::models::har::*;
+use crate::models::openapi::OpenAPISpec;
+use crate::models::postman::{self, Item, PostmanCollection};
+use crate::converters::postman::convert_postman_to_openapi;
+use crate::utils::path::{collapse_path_template, split_url};
+use indexmap::IndexMap;
+use serde_json::Value;
+
+// Headers that browsers and proxies add to every request; they describe the
+// client rather than the API and would drown out the real parameters.
+const IGNORED_HEADERS: &[&str] = &[
+    "accept",
+    "accept-encoding",
+    "accept-language",
+    "cache-control",
+    "connection",
+    "content-length",
+    "cookie",
+    "dnt",
+    "host",
+    "origin",
+    "pragma",
+    "priority",
+    "referer",
+    "te",
+    "upgrade-insecure-requests",
+    "user-agent",
+];
+
+// Response types of the images, stylesheets, scripts, fonts and media a
+// page loads, as opposed to API calls.
+const ASSET_MIME_TYPES: &[&str] = &[
+    "image/",
+    "font/",
+    "audio/",
+    "video/",
+    "text/css",
+    "text/javascript",
+    "application/javascript",
+    "application/x-javascript",
+    "application/font-",
+    "application/x-font-",
+    "application/vnd.ms-fontobject",
+    "application/wasm",
+];
+
+const ASSET_EXTENSIONS: &[&str] = &[
+    "css", "js", "mjs", "map", "png", "jpg", "jpeg", "gif", "svg", "ico", "webp", "avif", "bmp",
+    "woff", "woff2", "ttf", "otf", "eot", "mp3", "mp4", "webm", "wav", "wasm",
+];
+
+fn mime_essence(mime_type: &str) -> String {
+    mime_type.split(';').next().unwrap_or("").trim().to_lowercase()
+}
+
+fn is_ignored_header(name: &str) -> bool {
+    let name = name.to_lowercase();
+    name.starts_with(':') || name.starts_with("sec-") || IGNORED_HEADERS.contains(&name.as_str())
+}
+
+/// Whether an entry fetched a static asset, judged by the response's MIME
+/// type or else the URL's file extension.
+pub fn is_static_asset(entry: &Entry) -> bool {
+    let mime_type = entry.response.content.as_ref()
+        .and_then(|c| c.mime_type.as_deref())
+        .map(mime_essence)
+        .unwrap_or_default();
+    if ASSET_MIME_TYPES.iter().any(|asset| mime_type.starts_with(asset)) {
+        return true;
+    }
+    let path = split_url(&entry.request.url).path;
+    let file = path.rsplit('/').next().unwrap_or("");
+    file.rsplit_once('.')
+        .is_some_and(|(_, extension)| ASSET_EXTENSIONS.contains(&extension.to_lowercase().as_str()))
+}
+
+fn build_request(entry: &Entry, segments: &[String], variables: &[(String, String)]) -> postman::Request {
+    let request = &entry.request;
+
+    let mut headers: Vec<postman::Header> = request.headers.iter()
+        .filter(|h| !is_ignored_header(&h.name) || h.name.eq_ignore_ascii_case("content-type"))
+        .map(|h| if h.name.eq_ignore_ascii_case("content-type") {
+            postman::Header::new(&h.name, &mime_essence(&h.value))
+        } else {
+            postman::Header::new(&h.name, &h.value)
+        })
+        .collect();
+
+    let body = request.post_data.as_ref().and_then(|post_data| {
+        let text = post_data.text.as_ref()?;
+        if let Some(mime_type) = &post_data.mime_type {
+            if !headers.iter().any(|h| h.is("content-type")) {
+                headers.push(postman::Header::new("Content-Type", &mime_essence(mime_type)));
+            }
+        }
+        Some(postman::Body {
+            mode: Some("raw".to_string()),
+            raw: Some(text.clone()),
+            urlencoded: None,
+            formdata: None,
+        })
+    });
+
+    let query = request.query_string.iter()
+        .map(|q| postman::Query {
+            key: Some(q.name.clone()),
+            value: Some(q.value.clone()),
+            description: None,
+            disabled: None,
+        })
+        .collect();
+
+    postman::Request {
+        method: Some(request.method.to_uppercase()),
+        url: Some(postman::Url {
+            path: Some(segments.iter().map(|s| Value::String(s.clone())).collect()),
+            variable: Some(variables.iter()
+                .map(|(key, value)| postman::Variable {
+                    key: Some(key.clone()),
+                    value: Some(value.clone()),
+                })
+                .collect()),
+            query: Some(query),
+        }),
+        header: Some(headers),
+        body,
+        description: None,
+    }
+}
+
+fn build_response(entry: &Entry) -> postman::Response {
+    let response = &entry.response;
+    let content = response.content.as_ref();
+
+    let mime_type = content
+        .and_then(|c| c.mime_type.as_deref())
+        .map(mime_essence)
+        .unwrap_or_else(|| "application/json".to_string());
+
+    // Base64 payloads are binary captures; keep the schema but not the bytes.
+    let body = content
+        .filter(|c| c.encoding.as_deref() != Some("base64"))
+        .and_then(|c| c.text.clone());
+
+    let name = match &response.status_text {
+        Some(text) if !text.is_empty() => format!("{} {}", response.status, text),
+        _ => response.status.to_string(),
+    };
+
+    postman::Response {
+        name: Some(name),
+        code: Some(response.status),
+        header: Some(vec![postman::Header {
+            key: Some("Content-Type".to_string()),
+            value: Some(mime_type),
+            description: None,
+            disabled: None,
+        }]),
+        body,
+        original_request: None,
+    }
+}
+
+/// Merges a repeated call into an already collected request, picking up
+/// query parameters and a request body the first call did not have. Query
+/// parameters that are not sent on every call are marked as disabled so the
+/// converter documents them as optional.
+pub(crate) fn merge_request(existing: &mut postman::Request, incoming: postman::Request) {
+    if let (Some(url), Some(incoming_url)) = (existing.url.as_mut(), incoming.url) {
+        let incoming_queries = incoming_url.query.unwrap_or_default();
+        let queries = url.query.get_or_insert_with(Vec::new);
+        for query in queries.iter_mut() {
+            if !incoming_queries.iter().any(|q| q.key == query.key) {
+                query.disabled = Some(true);
+            }
+        }
+        for mut query in incoming_queries {
+            if !queries.iter().any(|q| q.key == query.key) {
+                query.disabled = Some(true);
+                queries.push(query);
+            }
+        }
+    }
+
+    if existing.body.is_none() && incoming.body.is_some() {
+        existing.body = incoming.body;
+        existing.header = incoming.header;
+    }
+}
+
+/// Builds a Postman collection from recorded traffic. Calls that share a
+/// method and a templated path are folded into a single item so they end up
+/// as one operation, with one saved response per observed status code.
+/// Static assets are left out unless `keep_static_assets` is set.
+pub fn convert_har_to_postman(har: &Har, keep_static_assets: bool) -> PostmanCollection {
+    let mut hosts: IndexMap<String, IndexMap<(String, String), Item>> = IndexMap::new();
+
+    for entry in &har.log.entries {
+        // Aborted or blocked requests never got an answer from the server
+        if entry.response.status == 0 {
+            continue;
+        }
+        if !keep_static_assets && is_static_asset(entry) {
+            continue;
+        }
+        let split = split_url(&entry.request.url);
+        let (segments, variables) = collapse_path_template(split.path);
+        let method = entry.request.method.to_uppercase();
+        let key = (method.clone(), segments.join("/"));
+
+        let request = build_request(entry, &segments, &variables);
+        let response = build_response(entry);
+
+        let items = hosts.entry(split.host.to_string()).or_default();
+        match items.get_mut(&key) {
+            Some(item) => {
+                if let Some(existing) = item.request.as_mut() {
+                    merge_request(existing, request);
+                }
+                let responses = item.response.get_or_insert_with(Vec::new);
+                if !responses.iter().any(|r| r.code == response.code) {
+                    responses.push(response);
+                }
+            }
+            None => {
+                let display_path: Vec<String> = segments.iter()
+                    .map(|s| match s.strip_prefix(':') {
+                        Some(name) => format!("{{{}}}", name),
+                        None => s.clone(),
+                    })
+                    .collect();
+                let item = Item {
+                    name: Some(format!("{} /{}", method, display_path.join("/"))),
+                    item: None,
+                    request: Some(request),
+                    response: Some(vec![response]),
+                    event: None,
+                };
+                items.insert(key, item);
+            }
+        }
+    }
+
+    let folders = hosts.into_iter()
+        .map(|(host, items)| Item {
+            name: Some(host),
+            item: Some(items.into_values().collect()),
+            request: None,
+            response: None,
+            event: None,
+        })
+        .collect();
+
+    let title = har.log.pages.as_ref()
+        .and_then(|pages| pages.first())
+        .and_then(|page| page.title.clone())
+        .unwrap_or_else(|| "HAR Capture".to_string());
+
+    PostmanCollection {
+        info: Some(postman::Info {
+            name: Some(title),
+            description: Some("Generated from recorded HTTP traffic".to_string()),
+            version: None,
+        }),
+        item: Some(folders),
+        event: None,
+    }
+}
+
+pub fn convert_har_to_openapi(har: &Har) -> OpenAPISpec {
+    convert_postman_to_openapi(&convert_har_to_postman(har, false))
+}
\ No newline at end of file
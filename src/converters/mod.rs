@@ -1 +1,4 @@
-pub mod postman;
\ No newline at end of file
+pub mod postman;
+pub mod har;
+pub mod curl;
+pub mod bruno;
\ No newline at end of file
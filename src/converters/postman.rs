@@ -1,6 +1,7 @@
 use crate::models::postman::*;
 use crate::models::openapi::*;
 use crate::utils::schema::infer_schema;
+use crate::utils::scripts::{schema_assertions, status_assertions};
 use std::collections::{HashMap, HashSet};
 use indexmap::IndexMap;
 use serde_json::{self, Value};
@@ -11,8 +12,11 @@ fn process_request_body(request: &Request) -> Option<RequestBody> {
         None => return None,
     };
     
-    if body.mode.as_deref() != Some("raw") {
-        return None;
+    match body.mode.as_deref() {
+        Some("raw") => {},
+        Some("urlencoded") => return process_form_body(body.urlencoded.as_ref()?, "application/x-www-form-urlencoded"),
+        Some("formdata") => return process_form_body(body.formdata.as_ref()?, "multipart/form-data"),
+        _ => return None,
     }
     
     // Find content type header
@@ -47,6 +51,7 @@ fn process_request_body(request: &Request) -> Option<RequestBody> {
                 properties: None,
                 required: None,
                 items: None,
+                keywords: IndexMap::new(),
             },
             example: Some(Value::String(raw.to_string())),
             examples: None,
@@ -59,6 +64,36 @@ fn process_request_body(request: &Request) -> Option<RequestBody> {
     })
 }
 
+fn process_form_body(fields: &[FormParameter], content_type: &str) -> Option<RequestBody> {
+    let mut example = serde_json::Map::new();
+    
+    for field in fields {
+        if field.disabled.unwrap_or(false) {
+            continue;
+        }
+        if let Some(key) = &field.key {
+            example.insert(key.clone(), Value::String(field.value.clone().unwrap_or_default()));
+        }
+    }
+    
+    if example.is_empty() {
+        return None;
+    }
+    
+    let example = Value::Object(example);
+    let mut content = HashMap::new();
+    content.insert(content_type.to_string(), Content {
+        schema: infer_schema(&example),
+        example: Some(example),
+        examples: None,
+    });
+    
+    Some(RequestBody {
+        content,
+        required: true,
+    })
+}
+
 fn process_response(response: &Response) -> OpenAPIResponse {
     let _status_code = response.code.unwrap_or(200);
     
@@ -83,6 +118,7 @@ fn process_response(response: &Response) -> OpenAPIResponse {
                         properties: None,
                         required: None,
                         items: None,
+                        keywords: IndexMap::new(),
                     },
                     Value::String(body.to_string()),
                 )
@@ -95,6 +131,7 @@ fn process_response(response: &Response) -> OpenAPIResponse {
                 properties: None,
                 required: None,
                 items: None,
+                keywords: IndexMap::new(),
             },
             Value::String(body.to_string()),
         )
@@ -107,7 +144,7 @@ fn process_response(response: &Response) -> OpenAPIResponse {
     
     let summary = response.name.clone().unwrap_or_else(|| "Example response".to_string());
     
-    let mut examples = HashMap::new();
+    let mut examples = IndexMap::new();
     examples.insert(example_name, Example {
         value: example.clone(),
         summary,
@@ -141,6 +178,7 @@ fn process_parameters(url_obj: &Url, headers: &Option<Vec<Header>>) -> Vec<Param
                         properties: None,
                         required: None,
                         items: None,
+                        keywords: IndexMap::new(),
                     },
                     description: None,
                     required: Some(true),
@@ -161,9 +199,10 @@ fn process_parameters(url_obj: &Url, headers: &Option<Vec<Header>>) -> Vec<Param
                         properties: None,
                         required: None,
                         items: None,
+                        keywords: IndexMap::new(),
                     },
                     description: query.description.clone(),
-                    required: Some(query.disabled.unwrap_or(false) == false),
+                    required: Some(!query.disabled.unwrap_or(false)),
                 });
             }
         }
@@ -182,9 +221,10 @@ fn process_parameters(url_obj: &Url, headers: &Option<Vec<Header>>) -> Vec<Param
                             properties: None,
                             required: None,
                             items: None,
+                            keywords: IndexMap::new(),
                         },
                         description: header.description.clone(),
-                        required: Some(header.disabled.unwrap_or(false) == false),
+                        required: Some(!header.disabled.unwrap_or(false)),
                     });
                 }
             }
@@ -194,6 +234,79 @@ fn process_parameters(url_obj: &Url, headers: &Option<Vec<Header>>) -> Vec<Param
     parameters
 }
 
+/// Description for a status code documented only by a test script.
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        202 => "Accepted",
+        204 => "No Content",
+        301 => "Moved Permanently",
+        302 => "Found",
+        304 => "Not Modified",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        410 => "Gone",
+        415 => "Unsupported Media Type",
+        422 => "Unprocessable Entity",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "Response",
+    }
+}
+
+/// Replaces the JSON schema of a response with `schema`, or gives it one
+/// when it has no body yet.
+fn apply_asserted_schema(response: &mut OpenAPIResponse, schema: &Schema) {
+    if response.content.is_empty() {
+        response.content.insert("application/json".to_string(), Content {
+            schema: schema.clone(),
+            example: None,
+            examples: None,
+        });
+        return;
+    }
+    for (content_type, content) in response.content.iter_mut() {
+        if content_type.contains("json") {
+            content.schema = schema.clone();
+        }
+    }
+}
+
+/// OpenAPI path of a request URL, with `:id` segments as `{id}`.
+pub fn path_template(url_obj: &Url) -> String {
+    let mut path_components = Vec::new();
+    
+    if let Some(path_parts) = &url_obj.path {
+        for component in path_parts {
+            match component {
+                Value::Object(obj) => {
+                    if let Some(Value::String(value)) = obj.get("value") {
+                        path_components.push(format!("{{{}}}", value));
+                    }
+                },
+                Value::String(s) => {
+                    if let Some(name) = s.strip_prefix(':') {
+                        path_components.push(format!("{{{}}}", name));
+                    } else {
+                        path_components.push(s.clone());
+                    }
+                },
+                _ => {}
+            }
+        }
+    }
+    
+    format!("/{}", path_components.join("/").trim_start_matches('/'))
+}
+
 pub fn convert_postman_to_openapi(postman_collection: &PostmanCollection) -> OpenAPISpec {
     let mut openapi = OpenAPISpec {
         openapi: "3.0.0".to_string(),
@@ -203,14 +316,25 @@ pub fn convert_postman_to_openapi(postman_collection: &PostmanCollection) -> Ope
                 .unwrap_or_else(|| "API Documentation".to_string()),
             description: postman_collection.info.as_ref()
                 .and_then(|info| info.description.clone())
-                .unwrap_or_else(|| "".to_string()),
-            version: "1.0.0".to_string(),
+                .unwrap_or_default(),
+            version: postman_collection.info.as_ref()
+                .and_then(|info| info.version.as_ref())
+                .map(|version| version.to_string())
+                .filter(|version| !version.is_empty())
+                .unwrap_or_else(|| "1.0.0".to_string()),
+            terms_of_service: None,
+            contact: None,
+            license: None,
+            extensions: IndexMap::new(),
         },
+        external_docs: None,
+        servers: Vec::new(),
         paths: IndexMap::new(),
         components: Components {
             schemas: IndexMap::new(),
         },
         tags: Vec::new(),
+        extensions: IndexMap::new(),
     };
     
     let mut tags_set = HashSet::new();
@@ -229,30 +353,7 @@ pub fn convert_postman_to_openapi(postman_collection: &PostmanCollection) -> Ope
         let method = request.method.as_deref().unwrap_or("GET").to_lowercase();
         
         if let Some(url_obj) = &request.url {
-            // Build path with {param} syntax
-            let mut path_components = Vec::new();
-            
-            if let Some(path_parts) = &url_obj.path {
-                for component in path_parts {
-                    match component {
-                        Value::Object(obj) => {
-                            if let Some(Value::String(value)) = obj.get("value") {
-                                path_components.push(format!("{{{}}}", value));
-                            }
-                        },
-                        Value::String(s) => {
-                            if s.starts_with(':') {
-                                path_components.push(format!("{{{}}}", &s[1..]));
-                            } else {
-                                path_components.push(s.clone());
-                            }
-                        },
-                        _ => {}
-                    }
-                }
-            }
-            
-            let path = format!("/{}", path_components.join("/").trim_start_matches('/'));
+            let path = path_template(url_obj);
             
             if !paths.contains_key(&path) {
                 paths.insert(path.clone(), IndexMap::new());
@@ -295,6 +396,15 @@ pub fn convert_postman_to_openapi(postman_collection: &PostmanCollection) -> Ope
                 }
             }
             
+            // Status codes the tests expect are documented even without examples
+            let test_script = item.script("test");
+            for status in test_script.as_deref().map(status_assertions).unwrap_or_default() {
+                responses.entry(status.to_string()).or_insert_with(|| OpenAPIResponse {
+                    description: reason_phrase(status).to_string(),
+                    content: HashMap::new(),
+                });
+            }
+            
             // If no responses, add a default 200 OK
             if responses.is_empty() {
                 responses.insert("200".to_string(), OpenAPIResponse {
@@ -303,6 +413,28 @@ pub fn convert_postman_to_openapi(postman_collection: &PostmanCollection) -> Ope
                 });
             }
             
+            // A schema the tests assert is more accurate than one inferred from
+            // examples. It goes to the status it is checked under, or else to
+            // the only success response; the first one asserted for a status wins.
+            let success: Vec<String> = responses.keys()
+                .filter(|status| status.starts_with('2') && *status != "204")
+                .cloned()
+                .collect();
+            let mut asserted_statuses = HashSet::new();
+            for assertion in test_script.as_deref().map(schema_assertions).unwrap_or_default() {
+                let status = match assertion.status {
+                    Some(status) => status.to_string(),
+                    None if success.len() == 1 => success[0].clone(),
+                    None => continue,
+                };
+                if status == "204" || !asserted_statuses.insert(status.clone()) {
+                    continue;
+                }
+                if let Some(response) = responses.get_mut(&status) {
+                    apply_asserted_schema(response, &Schema::from_json_schema(&assertion.schema));
+                }
+            }
+            
             // Add tags to the tag set
             for tag in current_tags {
                 tags_set.insert(tag.clone());
@@ -310,12 +442,13 @@ pub fn convert_postman_to_openapi(postman_collection: &PostmanCollection) -> Ope
             
             // Build the operation
             let operation = Operation {
-                summary: item.name.clone().unwrap_or_else(|| "".to_string()),
-                description: request.description.clone().unwrap_or_else(|| "".to_string()),
+                summary: item.name.clone().unwrap_or_default(),
+                description: request.description.clone().unwrap_or_default(),
                 parameters,
                 request_body,
                 responses,
                 tags: current_tags.to_vec(),
+                extensions: IndexMap::new(),
             };
             
             if let Some(path_map) = paths.get_mut(&path) {
@@ -332,7 +465,7 @@ pub fn convert_postman_to_openapi(postman_collection: &PostmanCollection) -> Ope
     ) {
         for item in items {
             if let Some(subitems) = &item.item {
-                let folder_name = item.name.clone().unwrap_or_else(|| "".to_string());
+                let folder_name = item.name.clone().unwrap_or_default();
                 let mut new_tags = current_tags.to_vec();
                 if !folder_name.is_empty() {
                     new_tags.push(folder_name);
@@ -357,4 +490,54 @@ pub fn convert_postman_to_openapi(postman_collection: &PostmanCollection) -> Ope
     openapi.tags.sort_by(|a, b| a.name.cmp(&b.name));
     
     openapi
-}
\ No newline at end of file
+}
+
+/// Extension names the scripts of each event are kept under.
+const SCRIPT_EXTENSIONS: &[(&str, &str)] = &[
+    ("prerequest", "x-postman-prerequest"),
+    ("test", "x-postman-test"),
+];
+
+/// Copies the collection's scripts onto `spec` so that they survive the
+/// conversion: collection scripts go on the document, and each operation
+/// gets its folders' and its own scripts, outermost first, in the order
+/// Postman runs them.
+pub fn attach_scripts(spec: &mut OpenAPISpec, collection: &PostmanCollection) {
+    for (listen, extension) in SCRIPT_EXTENSIONS {
+        if let Some(script) = collection.script(listen) {
+            spec.extensions.insert(extension.to_string(), Value::String(script));
+        }
+    }
+
+    fn attach_items(items: &[Item], folders: &[&Item], paths: &mut IndexMap<String, IndexMap<String, Operation>>) {
+        for item in items {
+            if let Some(children) = &item.item {
+                let mut nested = folders.to_vec();
+                nested.push(item);
+                attach_items(children, &nested, paths);
+                continue;
+            }
+            let Some(request) = &item.request else { continue };
+            let Some(url_obj) = &request.url else { continue };
+            let method = request.method.as_deref().unwrap_or("GET").to_lowercase();
+            let Some(operation) = paths.get_mut(&path_template(url_obj)).and_then(|ops| ops.get_mut(&method)) else {
+                continue;
+            };
+            for (listen, extension) in SCRIPT_EXTENSIONS {
+                let sources: Vec<String> = folders.iter().copied()
+                    .chain(std::iter::once(item))
+                    .filter_map(|scope| scope.script(listen))
+                    .collect();
+                if sources.is_empty() {
+                    operation.extensions.shift_remove(*extension);
+                } else {
+                    operation.extensions.insert(extension.to_string(), Value::String(sources.join("\n")));
+                }
+            }
+        }
+    }
+
+    if let Some(items) = &collection.item {
+        attach_items(items, &[], &mut spec.paths);
+    }
+}
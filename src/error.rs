@@ -5,6 +5,7 @@ use std::fmt;
 pub enum ConversionError {
     FileError(std::io::Error),
     ParseError(serde_json::Error),
+    YamlError(serde_yaml::Error),
     InvalidFormat(String),
 }
 
@@ -13,6 +14,7 @@ impl fmt::Display for ConversionError {
         match self {
             ConversionError::FileError(e) => write!(f, "File error: {}", e),
             ConversionError::ParseError(e) => write!(f, "Parse error: {}", e),
+            ConversionError::YamlError(e) => write!(f, "YAML error: {}", e),
             ConversionError::InvalidFormat(msg) => write!(f, "Invalid format: {}", msg),
         }
     }
@@ -23,6 +25,7 @@ impl Error for ConversionError {
         match self {
             ConversionError::FileError(e) => Some(e),
             ConversionError::ParseError(e) => Some(e),
+            ConversionError::YamlError(e) => Some(e),
             ConversionError::InvalidFormat(_) => None,
         }
     }
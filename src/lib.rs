@@ -2,6 +2,13 @@ pub mod models;
 pub mod converters;
 pub mod utils;
 pub mod error;
+pub mod config;
 
 pub use converters::postman::convert_postman_to_openapi;
-pub use models::postman::parse_postman_collection;
\ No newline at end of file
+pub use converters::har::{convert_har_to_openapi, convert_har_to_postman};
+pub use converters::curl::{convert_curl_to_openapi, convert_curl_to_postman};
+pub use converters::bruno::{convert_bruno_to_openapi, convert_bruno_to_postman};
+pub use models::postman::parse_postman_collection;
+pub use models::har::parse_har;
+pub use models::curl::parse_curl_commands;
+pub use models::bruno::parse_bruno_collection;
\ No newline at end of file
@@ -1,162 +1,1253 @@
+use std::collections::HashMap;
 use std::fs::{self, File, create_dir_all};
-use std::io::Write;
+use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use std::process;
-use clap::Parser;
-use postman_to_swagger::{convert_postman_to_openapi, parse_postman_collection};
+use std::time::Instant;
+use clap::{Parser, Subcommand, ValueEnum};
+use glob::Pattern;
+use indexmap::IndexMap;
+use postman_to_swagger::{
+    convert_bruno_to_postman, convert_curl_to_postman, convert_har_to_postman, convert_postman_to_openapi,
+    parse_bruno_collection, parse_curl_commands, parse_har, parse_postman_collection,
+};
+use postman_to_swagger::config::{find_config, CollectionConfig, InfoOverrides, OutputFormat, ProjectConfig};
+use postman_to_swagger::converters::postman::attach_scripts;
+use postman_to_swagger::models::postman::PostmanCollection;
+use postman_to_swagger::error::ConversionError;
+use postman_to_swagger::models::openapi::{load_openapi_document, Contact, ExternalDocs, License, OpenAPISpec};
+use postman_to_swagger::utils::codegen::{self, build_model};
+use postman_to_swagger::utils::contract::{build_plan, render_rust_tests};
+use postman_to_swagger::utils::json_schema::{export_json_schemas, use_type_arrays};
+use postman_to_swagger::utils::merge::merge_specs;
+use postman_to_swagger::utils::mock::MockServer;
+use postman_to_swagger::utils::preview::{Preview, PreviewUi};
+use postman_to_swagger::utils::report::{FileStatus, FileSummary, RunSummary};
+use postman_to_swagger::utils::redact::Redactor;
+use postman_to_swagger::utils::sanitize::{SanitizeConfig, Sanitizer};
+use postman_to_swagger::utils::update::update_document;
+use postman_to_swagger::utils::diff::diff_documents;
+use postman_to_swagger::utils::docs::{render_html, render_markdown};
+use postman_to_swagger::utils::examples::{check_examples, ExampleAction};
+use postman_to_swagger::utils::verify::{Verifier, VerifyReport};
+use postman_to_swagger::utils::watch::Watcher;
+use postman_to_swagger::utils::validate::{has_errors, validate_document, Severity};
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use serde::Serialize;
+use serde_json::Value;
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum InputFormat {
+    /// Postman collection (v2.x JSON export)
+    Postman,
+    /// HTTP Archive captured by a browser or proxy
+    Har,
+    /// Text file of `curl` commands, one per line (continuations allowed)
+    Curl,
+    /// Bruno collection folder (contains `bruno.json` and `.bru` files)
+    Bruno,
+}
+
+impl InputFormat {
+    /// What batch mode looks for in the input directory, for messages
+    fn description(self) -> &'static str {
+        match self {
+            InputFormat::Postman => ".json files",
+            InputFormat::Har => ".har files",
+            InputFormat::Curl => ".curl files",
+            InputFormat::Bruno => "Bruno collection folders",
+        }
+    }
+
+    /// Whether batch mode should convert this directory entry
+    fn accepts(self, path: &Path) -> bool {
+        let extension = path.extension().and_then(|s| s.to_str());
+        match self {
+            InputFormat::Postman => extension == Some("json"),
+            InputFormat::Har => extension == Some("har"),
+            InputFormat::Curl => extension == Some("curl"),
+            InputFormat::Bruno => path.join("bruno.json").is_file(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ExamplesMode {
+    /// Report examples that don't match their schema
+    Report,
+    /// Remove examples that don't match their schema
+    Drop,
+    /// Rewrite examples until they match their schema
+    Fix,
+}
+
+impl ExamplesMode {
+    fn action(self) -> ExampleAction {
+        match self {
+            ExamplesMode::Report => ExampleAction::Report,
+            ExamplesMode::Drop => ExampleAction::Drop,
+            ExamplesMode::Fix => ExampleAction::Fix,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum SpecFormat {
+    Yaml,
+    Json,
+    /// Markdown documentation grouped by tag
+    Markdown,
+    /// Self-contained HTML documentation grouped by tag
+    Html,
+}
+
+impl SpecFormat {
+    fn output_format(self) -> OutputFormat {
+        match self {
+            SpecFormat::Yaml => OutputFormat::Yaml,
+            SpecFormat::Json => OutputFormat::Json,
+            SpecFormat::Markdown => OutputFormat::Markdown,
+            SpecFormat::Html => OutputFormat::Html,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum UiKind {
+    /// Swagger UI, with a selector for every spec
+    Swagger,
+    /// Redoc
+    Redoc,
+}
+
+impl UiKind {
+    fn preview_ui(self) -> PreviewUi {
+        match self {
+            UiKind::Swagger => PreviewUi::Swagger,
+            UiKind::Redoc => PreviewUi::Redoc,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Language {
+    /// Rust structs and enums deriving serde's `Serialize` and `Deserialize`
+    Rust,
+    /// TypeScript interfaces and a typed map of the operations
+    Typescript,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum TestFormat {
+    /// JSON test plan for any test runner
+    Plan,
+    /// Rust integration test file
+    Rust,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ReportFormat {
+    Text,
+    Markdown,
+    Json,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Check OpenAPI documents for structural and semantic errors
+    Validate {
+        /// OpenAPI YAML or JSON files to check
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+
+        /// What to do with examples that don't match their schema;
+        /// `drop` and `fix` rewrite the files
+        #[arg(long, value_enum, default_value = "report")]
+        examples: ExamplesMode,
+    },
+    /// Compare two OpenAPI documents and list breaking changes
+    Diff {
+        /// The previous spec
+        old: PathBuf,
+        /// The new spec
+        new: PathBuf,
+
+        /// Report format
+        #[arg(long, value_enum, default_value = "text")]
+        format: ReportFormat,
+    },
+    /// Run a fake backend answering from saved examples and schemas
+    Mock {
+        /// Collection (in the `--from` format) or OpenAPI spec to mock
+        input: PathBuf,
+
+        /// Port to listen on
+        #[arg(long, default_value_t = 4010)]
+        port: u16,
+
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+    },
+    /// Generate client types from the schemas of a spec or collection
+    Codegen {
+        /// Language to generate
+        #[arg(value_enum)]
+        language: Language,
+
+        /// Collection (in the `--from` format) or OpenAPI spec to read
+        input: PathBuf,
+
+        /// File to write [default: standard output]
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Generate contract tests from the saved responses of a collection
+    Contract {
+        /// Collection (in the `--from` format) with saved responses
+        input: PathBuf,
+
+        /// Kind of test suite
+        #[arg(long, value_enum, default_value = "plan")]
+        format: TestFormat,
+
+        /// Server the tests send requests to
+        #[arg(long, default_value = "http://127.0.0.1:4010")]
+        base_url: String,
+
+        /// File to write [default: standard output]
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Replay a collection's requests against a server and report where the
+    /// responses drift from the converted spec
+    Verify {
+        /// Collection (in the `--from` format) whose requests are replayed
+        input: PathBuf,
+
+        /// Server to send the requests to, e.g. http://127.0.0.1:4010
+        #[arg(long)]
+        base_url: String,
+
+        /// Value for a `{{NAME}}` variable or `:NAME` path segment (repeatable);
+        /// others are read from `CONTRACT_<NAME>`
+        #[arg(long = "var", value_name = "NAME=VALUE")]
+        vars: Vec<String>,
+
+        /// Report format
+        #[arg(long, value_enum, default_value = "text")]
+        format: ReportFormat,
+    },
+    /// Browse the generated specs in Swagger UI or Redoc on a local server
+    Serve {
+        /// Directory of specs to serve [default: the output directory]
+        #[arg(long)]
+        dir: Option<PathBuf>,
+
+        /// Port to listen on
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+
+        /// Documentation viewer
+        #[arg(long, value_enum, default_value = "swagger")]
+        ui: UiKind,
+    },
+}
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
-    /// Input Postman collection filename
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Input collection filename (a folder name for Bruno collections)
     #[arg(long)]
     input: Option<String>,
 
-    /// Output OpenAPI YAML filename
+    /// Output OpenAPI filename
     #[arg(long)]
     output: Option<String>,
 
-    /// Input directory for Postman collections
-    #[arg(long, default_value = "collections")]
-    input_dir: String,
+    /// Input directory for Postman collections [default: collections]
+    #[arg(long)]
+    input_dir: Option<String>,
+
+    /// Output directory for OpenAPI specs [default: output]
+    #[arg(long)]
+    output_dir: Option<String>,
+
+    /// Project configuration file [default: ./postman2openapi.toml if present]
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Format of the written specs, or documentation instead [default: yaml]
+    #[arg(long, value_enum)]
+    format: Option<SpecFormat>,
+
+    /// Format of the input files
+    #[arg(long, value_enum, default_value = "postman")]
+    from: InputFormat,
+
+    /// Combine every collection in the input directory into one spec
+    #[arg(long, conflicts_with = "input")]
+    merge: bool,
+
+    /// Title of the combined spec (defaults to the joined collection titles)
+    #[arg(long, requires = "merge")]
+    title: Option<String>,
+
+    /// Merge the conversion into this existing spec, keeping hand edits
+    #[arg(long)]
+    update: Option<PathBuf>,
+
+    /// Don't write specs that fail validation
+    #[arg(long, conflicts_with = "no_validate")]
+    strict: bool,
+
+    /// Skip validating converted specs
+    #[arg(long)]
+    no_validate: bool,
+
+    /// What to do with saved examples that don't match their schema
+    #[arg(long, value_enum, default_value = "report")]
+    examples: ExamplesMode,
+
+    /// Keep tokens, keys and passwords instead of redacting them
+    #[arg(long)]
+    no_redact: bool,
+
+    /// Extra regular expression whose matches are redacted (repeatable)
+    #[arg(long = "redact-pattern", value_name = "REGEX")]
+    redact_patterns: Vec<String>,
+
+    /// TOML file with PII masking and example truncation rules
+    #[arg(long)]
+    sanitize_config: Option<PathBuf>,
+
+    /// `info.version` of the specs (defaults to the collection version)
+    #[arg(long)]
+    api_version: Option<String>,
+
+    /// `info.contact.name`
+    #[arg(long)]
+    contact_name: Option<String>,
+
+    /// `info.contact.email`
+    #[arg(long)]
+    contact_email: Option<String>,
+
+    /// `info.contact.url`
+    #[arg(long)]
+    contact_url: Option<String>,
+
+    /// `info.license.name`
+    #[arg(long)]
+    license_name: Option<String>,
+
+    /// `info.license.url`
+    #[arg(long, requires = "license_name")]
+    license_url: Option<String>,
+
+    /// `info.termsOfService`
+    #[arg(long)]
+    terms_of_service: Option<String>,
+
+    /// URL of external documentation (`externalDocs.url`)
+    #[arg(long)]
+    external_docs: Option<String>,
+
+    /// Extension added to `info`, value parsed as JSON or kept as a string (repeatable)
+    #[arg(long = "info-extension", value_name = "x-NAME=VALUE")]
+    info_extensions: Vec<String>,
+
+    /// Also convert inputs in subdirectories of the input directory
+    #[arg(long, short = 'r')]
+    recursive: bool,
+
+    /// Only convert inputs whose path relative to the input directory matches (repeatable)
+    #[arg(long, value_name = "GLOB")]
+    include: Vec<String>,
+
+    /// Skip inputs whose path relative to the input directory matches (repeatable)
+    #[arg(long, value_name = "GLOB")]
+    exclude: Vec<String>,
+
+    /// Number of conversion threads [default: one per core]
+    #[arg(long, short = 'j')]
+    jobs: Option<usize>,
+
+    /// Write the batch summary as JSON to this file
+    #[arg(long, value_name = "PATH")]
+    report: Option<PathBuf>,
+
+    /// Keep running and regenerate specs whose inputs change
+    #[arg(long, short = 'w')]
+    watch: bool,
+
+    /// Also write each request and response body as a JSON Schema file,
+    /// in a `<spec name>.schemas` directory next to the spec
+    #[arg(long)]
+    json_schema: bool,
+
+    /// URL the JSON Schema `$id`s start with [default: none, so they are relative]
+    #[arg(long, value_name = "URL", requires = "json_schema")]
+    json_schema_base: Option<String>,
+
+    /// Keep images, stylesheets, scripts and fonts recorded in a HAR file
+    #[arg(long)]
+    keep_static_assets: bool,
+
+    /// Keep the collection's pre-request and test scripts as
+    /// `x-postman-prerequest` and `x-postman-test` extensions
+    #[arg(long)]
+    keep_scripts: bool,
+}
+
+/// Reads any supported input into the Postman model the converter works on.
+fn load_collection(path: &Path, cli: &Cli) -> Result<PostmanCollection, ConversionError> {
+    match cli.from {
+        InputFormat::Postman => parse_postman_collection(path),
+        InputFormat::Har => parse_har(path).map(|har| convert_har_to_postman(&har, cli.keep_static_assets)),
+        InputFormat::Curl => parse_curl_commands(path).map(|commands| {
+            for warning in commands.iter().flat_map(|c| &c.warnings) {
+                eprintln!("Warning: {}: {}", path.display(), warning);
+            }
+            convert_curl_to_postman(&commands)
+        }),
+        InputFormat::Bruno => parse_bruno_collection(path).map(|c| convert_bruno_to_postman(&c)),
+    }
+}
+
+/// Reads an OpenAPI spec as is, or converts any other supported input.
+fn load_document(path: &Path, cli: &Cli) -> Result<Value, ConversionError> {
+    load_openapi_document(path).or_else(|_| {
+        load_collection(path, cli)
+            .map(|collection| convert_postman_to_openapi(&collection))
+            .and_then(|spec| serde_json::to_value(&spec).map_err(ConversionError::ParseError))
+    })
+}
+
+/// Output for one input, printed in one piece so that parallel
+/// conversions don't interleave their messages.
+#[derive(Default)]
+struct Log {
+    lines: Vec<(bool, String)>,
+}
+
+impl Log {
+    fn info(&mut self, line: impl Into<String>) {
+        self.lines.push((false, line.into()));
+    }
+
+    fn error(&mut self, line: impl Into<String>) {
+        self.lines.push((true, line.into()));
+    }
+
+    fn flush(&mut self) {
+        let mut stdout = io::stdout().lock();
+        let mut stderr = io::stderr().lock();
+        for (is_error, line) in self.lines.drain(..) {
+            let _ = if is_error { writeln!(stderr, "{}", line) } else { writeln!(stdout, "{}", line) };
+        }
+        let _ = stdout.flush();
+    }
+}
+
+/// Everything that turns one input into a finished spec, shared by all
+/// inputs of a run.
+struct Pipeline<'a> {
+    cli: &'a Cli,
+    config: ProjectConfig,
+    info_flags: InfoOverrides,
+    redactor: Option<Redactor>,
+    sanitizer: Option<Sanitizer>,
+    output_dir: PathBuf,
+    format: OutputFormat,
+}
+
+impl Pipeline<'_> {
+    /// Converts one input and applies every override. Also returns the
+    /// collection's own name, which the overrides may have replaced as title.
+    fn convert(&self, path: &Path, log: &mut Log) -> Result<(String, OpenAPISpec), ConversionError> {
+        let mut collection = load_collection(path, self.cli)?;
+        let name = collection.info.as_ref()
+            .and_then(|info| info.name.clone())
+            .unwrap_or_else(|| path.file_stem().and_then(|s| s.to_str()).unwrap_or("collection").to_string());
+        if let Some(redactor) = &self.redactor {
+            let redacted = redactor.clone().redact_collection(&mut collection);
+            if redacted > 0 {
+                log.info(format!("  redacted {} secret value(s)", redacted));
+            }
+        }
+        let mut spec = convert_postman_to_openapi(&collection);
+        if self.cli.keep_scripts || self.config.keep_scripts {
+            attach_scripts(&mut spec, &collection);
+        }
+        self.config.apply(&mut spec, self.config.collection(path));
+        self.info_flags.apply(&mut spec);
+        if let Some(sanitizer) = &self.sanitizer {
+            let report = sanitizer.sanitize_spec(&mut spec);
+            if report.masked + report.truncated + report.dropped > 0 {
+                log.info(format!(
+                    "  sanitized examples: {} value(s) masked, {} truncated, {} example(s) dropped",
+                    report.masked, report.truncated, report.dropped
+                ));
+            }
+        }
+        Ok((name, spec))
+    }
+
+    /// With `--json-schema`, writes the payload schemas of `spec` next to
+    /// the spec written to `output_path`.
+    fn write_json_schemas(&self, spec: &OpenAPISpec, output_path: &Path, log: &mut Log) {
+        if !self.cli.json_schema {
+            return;
+        }
+        let stem = output_path.file_stem().and_then(|s| s.to_str()).unwrap_or("spec");
+        let dir = output_path.with_file_name(format!("{}.schemas", stem));
+        let files = export_json_schemas(spec, self.cli.json_schema_base.as_deref().unwrap_or(""));
+        let written = create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))
+            .and_then(|_| files.iter().try_for_each(|file| write_spec(&file.document, &dir.join(&file.file_name))));
+        match written {
+            Ok(()) => log.info(format!("  wrote {} JSON Schema file(s) to {}", files.len(), dir.display())),
+            Err(e) => log.error(format!("  {}", e)),
+        }
+    }
+
+    /// Validates a converted spec, checks its examples and logs what was
+    /// found. Returns the document to write, or `None` when the spec has
+    /// errors and `--strict` forbids writing it.
+    fn check(&self, spec: &OpenAPISpec, log: &mut Log, summary: &mut FileSummary) -> Option<Value> {
+        let cli = self.cli;
+        let mut document = match serde_json::to_value(spec) {
+            Ok(document) => document,
+            Err(e) => {
+                log.error(format!("Failed to serialize spec: {}", e));
+                return None;
+            }
+        };
+        if spec.openapi.starts_with("3.1.") {
+            use_type_arrays(&mut document);
+        }
+        if cli.no_validate && cli.examples == ExamplesMode::Report {
+            return Some(document);
+        }
+        let mut issues = if cli.no_validate { Vec::new() } else { validate_document(&document) };
+        issues.extend(check_examples(&mut document, cli.examples.action()));
+        for issue in &issues {
+            log.error(format!("  {}", issue));
+        }
+        summary.errors = issues.iter().filter(|issue| issue.severity == Severity::Error).count();
+        summary.warnings = issues.len() - summary.errors;
+        if has_errors(&issues) && cli.strict {
+            log.error("Spec has validation errors; not writing it (--strict).");
+            return None;
+        }
+        Some(document)
+    }
+
+    /// Converts one input of a batch run. The spec is returned instead of
+    /// written with `--merge`.
+    fn convert_input(&self, path: &Path, input_dir: &Path) -> (FileSummary, Option<(String, OpenAPISpec)>) {
+        let started = Instant::now();
+        let relative = path.strip_prefix(input_dir).unwrap_or(path);
+        let mut summary = FileSummary::new(relative.display().to_string());
+        let mut log = Log::default();
+        let mut merged = None;
+
+        log.info(format!("Processing: {}", path.display()));
+        match self.convert(path, &mut log) {
+            Ok((name, spec)) => {
+                summary.operations = spec.paths.values().map(|operations| operations.len()).sum();
+                if self.cli.merge {
+                    summary.status = FileStatus::Merged;
+                    merged = Some((name, spec));
+                } else if let Some(document) = self.check(&spec, &mut log, &mut summary) {
+                    // Inputs in subdirectories keep their place under the output directory
+                    let output_dir = match relative.parent() {
+                        Some(parent) => self.output_dir.join(parent),
+                        None => self.output_dir.clone(),
+                    };
+                    let output_path = output_dir
+                        .join(default_output_name(path, self.config.collection(path), self.format));
+                    let written = create_dir_all(&output_dir)
+                        .map_err(|e| format!("Failed to create output directory: {}", e))
+                        .and_then(|_| write_spec(&document, &output_path));
+                    match written {
+                        Ok(()) => {
+                            log.info(format!("Converted to {}", output_path.display()));
+                            self.write_json_schemas(&spec, &output_path, &mut log);
+                            summary.status = FileStatus::Converted;
+                            summary.output = Some(output_path.display().to_string());
+                        }
+                        Err(e) => {
+                            log.error(e.clone());
+                            summary.message = Some(e);
+                        }
+                    }
+                } else {
+                    summary.status = FileStatus::Skipped;
+                }
+            }
+            Err(e) => {
+                log.error(format!("Error reading input file {}: {}", path.display(), e));
+                summary.message = Some(e.to_string());
+            }
+        }
+
+        summary.duration_ms = started.elapsed().as_millis() as u64;
+        log.flush();
+        (summary, merged)
+    }
+
+    /// Converts `--input` to `output_path`, or into the `--update` spec.
+    /// Returns whether a spec was written.
+    fn convert_single(&self, input_path: &Path, output_path: &Path) -> bool {
+        let cli = self.cli;
+        let mut log = Log::default();
+        log.info(format!("Processing: {}", input_path.display()));
+        let openapi_spec = match self.convert(input_path, &mut log) {
+            Ok((_, openapi_spec)) => openapi_spec,
+            Err(e) => {
+                log.error(format!("Error reading input file: {}", e));
+                log.flush();
+                return false;
+            }
+        };
+        let document = self.check(&openapi_spec, &mut log, &mut FileSummary::new(input_path.display().to_string()));
+        log.flush();
+        let Some(document) = document else {
+            return false;
+        };
+        let written = match &cli.update {
+            Some(existing_path) => update_spec(&document, existing_path, cli.output.as_ref().map(|_| output_path.to_path_buf()))
+                .inspect(|written| println!("Updated {}", written.display())),
+            None => write_spec(&document, output_path)
+                .map(|_| output_path.to_path_buf())
+                .inspect(|written| println!("Converted to {}", written.display())),
+        };
+        match written {
+            Ok(written) => {
+                self.write_json_schemas(&openapi_spec, &written, &mut log);
+                log.flush();
+                true
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                false
+            }
+        }
+    }
+
+    /// Converts `inputs` in parallel, prints the summary and, with
+    /// `--merge`, writes the combined spec. Returns false when the combined
+    /// spec couldn't be written.
+    fn convert_batch(&self, pool: &ThreadPool, inputs: &[PathBuf], input_dir: &Path) -> bool {
+        let cli = self.cli;
+        let started = Instant::now();
+        let results: Vec<(FileSummary, Option<(String, OpenAPISpec)>)> = pool.install(|| {
+            inputs.par_iter().map(|path| self.convert_input(path, input_dir)).collect()
+        });
+        let (files, specs): (Vec<_>, Vec<_>) = results.into_iter().unzip();
+        
+        let summary = RunSummary::new(files, started.elapsed());
+        println!("\nSummary:\n{}", summary.to_text().trim_end());
+        if let Some(report_path) = &cli.report {
+            match fs::write(report_path, summary.to_json()) {
+                Ok(()) => println!("Wrote report to {}", report_path.display()),
+                Err(e) => eprintln!("Failed to write report {}: {}", report_path.display(), e),
+            }
+        }
+        
+        let merged_specs: Vec<(String, OpenAPISpec)> = specs.into_iter().flatten().collect();
+        if !cli.merge || merged_specs.is_empty() {
+            return true;
+        }
+        
+        let files_processed = merged_specs.len();
+        let output_filename = cli.output.clone()
+            .unwrap_or_else(|| format!("merged_openapi.{}", self.format.extension()));
+        let output_path = self.output_dir.join(output_filename);
+        let mut merged = merge_specs(merged_specs);
+        self.config.apply_document(&mut merged);
+        self.info_flags.apply(&mut merged);
+        if let Some(title) = &cli.title {
+            merged.info.title = title.clone();
+        }
+        let mut log = Log::default();
+        let document = self.check(&merged, &mut log, &mut FileSummary::new(output_path.display().to_string()));
+        log.flush();
+        let Some(document) = document else {
+            return false;
+        };
+        let written = match &cli.update {
+            Some(existing_path) => update_spec(&document, existing_path, cli.output.as_ref().map(|_| output_path)),
+            None => write_spec(&document, &output_path).map(|_| output_path),
+        };
+        match written {
+            Ok(written) => {
+                println!("Merged {} collection(s) into {}", files_processed, written.display());
+                self.write_json_schemas(&merged, &written, &mut log);
+                log.flush();
+                true
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                false
+            }
+        }
+    }
+}
+
+/// Which directory entries batch mode converts.
+struct InputFilter {
+    from: InputFormat,
+    recursive: bool,
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+    /// The output directory, never searched for inputs
+    skip: Option<PathBuf>,
+}
+
+impl InputFilter {
+    fn new(cli: &Cli, config: &ProjectConfig, output_dir: &Path) -> Result<Self, String> {
+        let compile = |globs: Vec<&String>| globs.into_iter()
+            .map(|glob| Pattern::new(glob).map_err(|e| format!("Invalid glob '{}': {}", glob, e)))
+            .collect::<Result<Vec<_>, _>>();
+        Ok(InputFilter {
+            from: cli.from,
+            recursive: cli.recursive || config.recursive,
+            include: compile(config.include.iter().chain(&cli.include).collect())?,
+            exclude: compile(config.exclude.iter().chain(&cli.exclude).collect())?,
+            skip: output_dir.canonicalize().ok(),
+        })
+    }
+
+    /// Whether an accepted input, given relative to the input directory,
+    /// passes the include and exclude globs.
+    fn matches(&self, relative: &Path) -> bool {
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        (self.include.is_empty() || self.include.iter().any(|glob| glob.matches(&relative)))
+            && !self.exclude.iter().any(|glob| glob.matches(&relative))
+    }
+
+    /// Collects the inputs in `dir`, sorted, descending into subdirectories
+    /// with `--recursive`.
+    fn collect(&self, dir: &Path, input_dir: &Path, inputs: &mut Vec<PathBuf>) -> io::Result<()> {
+        let mut paths: Vec<PathBuf> = fs::read_dir(dir)?.flatten().map(|entry| entry.path()).collect();
+        paths.sort();
+        for path in paths {
+            if self.from.accepts(&path) {
+                if self.matches(path.strip_prefix(input_dir).unwrap_or(&path)) {
+                    inputs.push(path);
+                }
+            } else if self.recursive && path.is_dir() {
+                let hidden = path.file_name()
+                    .and_then(|s| s.to_str())
+                    .is_some_and(|name| name.starts_with('.'));
+                if !hidden && path.canonicalize().ok() != self.skip {
+                    self.collect(&path, input_dir, inputs)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes a spec in the format its extension names: JSON for `.json`,
+/// Markdown or HTML documentation for `.md` and `.html`, YAML otherwise.
+fn write_spec<T: Serialize>(spec: &T, output_path: &Path) -> Result<(), String> {
+    let contents = match output_path.extension().and_then(|s| s.to_str()) {
+        Some("json") => serde_json::to_string_pretty(spec)
+            .map_err(|e| format!("Failed to serialize to JSON: {}", e))?,
+        Some(extension @ ("md" | "html")) => {
+            let document = serde_json::to_value(spec)
+                .map_err(|e| format!("Failed to serialize spec: {}", e))?;
+            if extension == "md" { render_markdown(&document) } else { render_html(&document) }
+        }
+        _ => serde_yaml::to_string(spec)
+            .map_err(|e| format!("Failed to serialize to YAML: {}", e))?,
+    };
+    let mut file = File::create(output_path)
+        .map_err(|e| format!("Failed to create output file: {}", e))?;
+    file.write_all(contents.as_bytes())
+        .map_err(|e| format!("Failed to write to file: {}", e))
+}
+
+/// Reads the config given with `--config`, or the one in the working
+/// directory; without either every setting keeps its default. `announce`
+/// prints which file is used, for commands that don't write to stdout.
+fn load_config(path: Option<&Path>, announce: bool) -> ProjectConfig {
+    let path = match path {
+        Some(path) => Some(path.to_path_buf()),
+        None => find_config(Path::new(".")),
+    };
+    let Some(path) = path else {
+        return ProjectConfig::default();
+    };
+    match ProjectConfig::load(&path) {
+        Ok(config) => {
+            if announce {
+                println!("Using config {}", path.display());
+            }
+            config
+        }
+        Err(e) => {
+            eprintln!("Invalid config {}: {}", path.display(), e);
+            process::exit(1);
+        }
+    }
+}
+
+/// The redactor for `--redact-pattern` and the config's `[redact]` table,
+/// or `None` when redaction is turned off.
+fn redactor(cli: &Cli, config: &ProjectConfig) -> Option<Redactor> {
+    if cli.no_redact || !config.redact.enabled {
+        return None;
+    }
+    let patterns: Vec<String> = config.redact.patterns.iter()
+        .chain(&cli.redact_patterns)
+        .cloned()
+        .collect();
+    match Redactor::new(&patterns) {
+        Ok(redactor) => Some(redactor),
+        Err(e) => {
+            eprintln!("Invalid --redact-pattern: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Collects the `info` flags, which win over the config file.
+fn info_flags(cli: &Cli) -> Result<InfoOverrides, String> {
+    let contact = if cli.contact_name.is_some() || cli.contact_email.is_some() || cli.contact_url.is_some() {
+        Some(Contact {
+            name: cli.contact_name.clone(),
+            url: cli.contact_url.clone(),
+            email: cli.contact_email.clone(),
+        })
+    } else {
+        None
+    };
+    let license = cli.license_name.as_ref().map(|name| License {
+        name: name.clone(),
+        url: cli.license_url.clone(),
+    });
+    let external_docs = cli.external_docs.as_ref().map(|url| ExternalDocs {
+        url: url.clone(),
+        description: None,
+    });
+
+    let mut extensions = IndexMap::new();
+    for extension in &cli.info_extensions {
+        let Some((key, value)) = extension.split_once('=') else {
+            return Err(format!("--info-extension '{}' is not of the form x-NAME=VALUE", extension));
+        };
+        let value = serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.to_string()));
+        extensions.insert(key.trim().to_string(), value);
+    }
+
+    let flags = InfoOverrides {
+        version: cli.api_version.clone(),
+        terms_of_service: cli.terms_of_service.clone(),
+        contact,
+        license,
+        external_docs,
+        extensions,
+        ..InfoOverrides::default()
+    };
+    flags.check_extensions()?;
+    Ok(flags)
+}
+
+/// The filename a converted input is written to when `--output` isn't given.
+fn default_output_name(input: &Path, collection: Option<&CollectionConfig>, format: OutputFormat) -> String {
+    if let Some(output) = collection.and_then(|c| c.output.clone()) {
+        return output;
+    }
+    let base_name = input.file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("output");
+    format!("{}_openapi.{}", base_name, format.extension())
+}
+
+fn validate_files(files: &[PathBuf], examples: ExamplesMode) -> bool {
+    let mut valid = true;
+    for file in files {
+        match load_openapi_document(file) {
+            Ok(mut document) => {
+                let mut issues = validate_document(&document);
+                let example_issues = check_examples(&mut document, examples.action());
+                let rewrite = !example_issues.is_empty() && examples != ExamplesMode::Report;
+                issues.extend(example_issues);
+                for issue in &issues {
+                    println!("{}: {}", file.display(), issue);
+                }
+                if rewrite {
+                    match write_spec(&document, file) {
+                        Ok(()) => println!("{}: rewrote examples", file.display()),
+                        Err(e) => {
+                            eprintln!("{}", e);
+                            valid = false;
+                        }
+                    }
+                }
+                if has_errors(&issues) {
+                    valid = false;
+                } else {
+                    println!("{}: valid", file.display());
+                }
+            }
+            Err(e) => {
+                eprintln!("Error reading {}: {}", file.display(), e);
+                valid = false;
+            }
+        }
+    }
+    valid
+}
+
+/// Where the spec generated by the previous update is kept, next to the
+/// updated file, so the next update can tell hand edits from generator output.
+fn base_snapshot_path(spec_path: &Path) -> PathBuf {
+    let file_name = spec_path.file_name()
+        .and_then(|s| s.to_str())
+        .unwrap_or("openapi.yaml");
+    spec_path.with_file_name(format!(".{}.base", file_name))
+}
+
+/// Writes the generated document into the hand-edited spec at
+/// `existing_path` and returns the file that was written.
+fn update_spec(generated: &Value, existing_path: &Path, output_path: Option<PathBuf>) -> Result<PathBuf, String> {
+    let existing = load_openapi_document(existing_path)
+        .map_err(|e| format!("Error reading existing spec {}: {}", existing_path.display(), e))?;
+    let snapshot_path = base_snapshot_path(existing_path);
+    let base = if snapshot_path.exists() {
+        Some(load_openapi_document(&snapshot_path)
+            .map_err(|e| format!("Error reading base snapshot {}: {}", snapshot_path.display(), e))?)
+    } else {
+        None
+    };
+    let (updated, report) = update_document(&existing, base.as_ref(), generated);
+
+    let output_path = output_path.unwrap_or_else(|| existing_path.to_path_buf());
+    write_spec(&updated, &output_path)?;
+    write_spec(generated, &snapshot_path)?;
+
+    for operation in &report.added {
+        println!("  added: {}", operation);
+    }
+    for operation in &report.removed {
+        println!("  removed from collection (marked deprecated): {}", operation);
+    }
+    println!("  kept {} hand-edited value(s)", report.kept_edits);
 
-    /// Output directory for OpenAPI specs
-    #[arg(long, default_value = "output")]
-    output_dir: String,
+    Ok(output_path)
 }
 
 fn main() {
     let cli = Cli::parse();
     
+    match &cli.command {
+        Some(Command::Validate { files, examples }) => {
+            if !validate_files(files, *examples) {
+                process::exit(1);
+            }
+            return;
+        }
+        Some(Command::Diff { old, new, format }) => {
+            let load = |path: &PathBuf| load_openapi_document(path).unwrap_or_else(|e| {
+                eprintln!("Error reading {}: {}", path.display(), e);
+                process::exit(1);
+            });
+            let diff = diff_documents(&load(old), &load(new));
+            let report = match format {
+                ReportFormat::Text => diff.to_text(),
+                ReportFormat::Markdown => diff.to_markdown(),
+                ReportFormat::Json => diff.to_json(),
+            };
+            println!("{}", report.trim_end());
+            if diff.has_breaking() {
+                process::exit(1);
+            }
+            return;
+        }
+        Some(Command::Mock { input, port, host }) => {
+            let document = load_document(input, &cli).unwrap_or_else(|e| {
+                eprintln!("Error reading {}: {}", input.display(), e);
+                process::exit(1);
+            });
+            let mock = MockServer::new(document);
+            let address = format!("{}:{}", host, port);
+            println!("Mocking {} operation(s) at http://{}/ (Ctrl+C to stop)", mock.operations().len(), address);
+            for operation in mock.operations() {
+                println!("  {}", operation);
+            }
+            let served = mock.serve(&address, |method, url, reply| {
+                println!("{} {} -> {} ({})", method, url, reply.status, reply.source);
+            });
+            if let Err(e) = served {
+                eprintln!("Failed to start server on {}: {}", address, e);
+                process::exit(1);
+            }
+            return;
+        }
+        Some(Command::Codegen { language, input, output }) => {
+            let document = load_document(input, &cli).unwrap_or_else(|e| {
+                eprintln!("Error reading {}: {}", input.display(), e);
+                process::exit(1);
+            });
+            let model = build_model(&document);
+            let code = match language {
+                Language::Rust => codegen::rust::render(&model),
+                Language::Typescript => codegen::typescript::render(&model),
+            };
+            match output {
+                Some(path) => {
+                    if let Err(e) = fs::write(path, code) {
+                        eprintln!("Failed to write {}: {}", path.display(), e);
+                        process::exit(1);
+                    }
+                    println!("Wrote {} type(s) to {}", model.types.len(), path.display());
+                }
+                None => print!("{}", code),
+            }
+            return;
+        }
+        Some(Command::Contract { input, format, base_url, output }) => {
+            let mut collection = load_collection(input, &cli).unwrap_or_else(|e| {
+                eprintln!("Error reading {}: {}", input.display(), e);
+                process::exit(1);
+            });
+            // The tests end up in a repository, so secrets are redacted as in specs
+            let config = load_config(cli.config.as_deref(), output.is_some());
+            if let Some(mut redactor) = redactor(&cli, &config) {
+                redactor.redact_collection(&mut collection);
+            }
+            let plan = build_plan(&collection, base_url);
+            if plan.cases.is_empty() {
+                eprintln!("{} has no saved responses to build contract tests from.", input.display());
+                process::exit(1);
+            }
+            let suite = match format {
+                TestFormat::Plan => serde_json::to_string_pretty(&plan).unwrap_or_default() + "\n",
+                TestFormat::Rust => render_rust_tests(&plan),
+            };
+            match output {
+                Some(path) => {
+                    if let Err(e) = fs::write(path, suite) {
+                        eprintln!("Failed to write {}: {}", path.display(), e);
+                        process::exit(1);
+                    }
+                    println!("Wrote {} contract test(s) to {}", plan.cases.len(), path.display());
+                }
+                None => print!("{}", suite),
+            }
+            return;
+        }
+        Some(Command::Verify { input, base_url, vars, format }) => {
+            let collection = load_collection(input, &cli).unwrap_or_else(|e| {
+                eprintln!("Error reading {}: {}", input.display(), e);
+                process::exit(1);
+            });
+            let mut variables = HashMap::new();
+            for var in vars {
+                let Some((name, value)) = var.split_once('=') else {
+                    eprintln!("--var '{}' is not of the form NAME=VALUE", var);
+                    process::exit(1);
+                };
+                variables.insert(name.trim().to_string(), value.to_string());
+            }
+            let verifier = Verifier::new(&collection, base_url, variables);
+            let report = verifier.run(&collection, |outcome| {
+                // Text reports are printed as the requests finish
+                if *format == ReportFormat::Text {
+                    print!("{}", VerifyReport::outcome_text(outcome));
+                }
+            });
+            let output = match format {
+                ReportFormat::Text => report.summary(),
+                ReportFormat::Markdown => report.to_markdown(),
+                ReportFormat::Json => report.to_json(),
+            };
+            println!("{}", output.trim_end());
+            if !report.passed() {
+                process::exit(1);
+            }
+            return;
+        }
+        Some(Command::Serve { dir, port, host, ui }) => {
+            let dir = dir.clone().unwrap_or_else(|| {
+                let config = load_config(cli.config.as_deref(), true);
+                PathBuf::from(cli.output_dir.clone().or(config.output_dir).unwrap_or_else(|| "output".to_string()))
+            });
+            if !dir.is_dir() {
+                eprintln!("Spec directory '{}' does not exist. Convert some collections first.", dir.display());
+                process::exit(1);
+            }
+            let preview = Preview::new(dir.clone(), ui.preview_ui());
+            let address = format!("{}:{}", host, port);
+            println!("Serving {} spec(s) from {} at http://{}/ (Ctrl+C to stop)", preview.specs().len(), dir.display(), address);
+            if let Err(e) = preview.serve(&address) {
+                eprintln!("Failed to start server on {}: {}", address, e);
+                process::exit(1);
+            }
+            return;
+        }
+        None => {}
+    }
+    
+    let config = load_config(cli.config.as_deref(), true);
+    let input_dir = cli.input_dir.clone()
+        .or_else(|| config.input_dir.clone())
+        .unwrap_or_else(|| "collections".to_string());
+    let output_dir = cli.output_dir.clone()
+        .or_else(|| config.output_dir.clone())
+        .unwrap_or_else(|| "output".to_string());
+    let format = cli.format.map(SpecFormat::output_format)
+        .or(config.format)
+        .unwrap_or_default();
+    
+    let info_flags = info_flags(&cli).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
+    
+    let redactor = redactor(&cli, &config);
+    
+    let sanitize_config = match &cli.sanitize_config {
+        Some(path) => Some(SanitizeConfig::load(path).unwrap_or_else(|e| {
+            eprintln!("Invalid sanitize config: {}", e);
+            process::exit(1);
+        })),
+        None => config.sanitize.clone(),
+    };
+    let sanitizer = sanitize_config.map(|sanitize_config| {
+        Sanitizer::new(&sanitize_config).unwrap_or_else(|e| {
+            eprintln!("Invalid sanitize pattern: {}", e);
+            process::exit(1);
+        })
+    });
+    
+    if cli.update.is_some() && cli.input.is_none() && !cli.merge {
+        eprintln!("--update needs a single --input or --merge, since it updates one spec.");
+        process::exit(1);
+    }
+    
     // Create output directory if it doesn't exist
-    if !Path::new(&cli.output_dir).exists() {
-        if let Err(e) = create_dir_all(&cli.output_dir) {
+    if !Path::new(&output_dir).exists() {
+        if let Err(e) = create_dir_all(&output_dir) {
             eprintln!("Failed to create output directory: {}", e);
             process::exit(1);
         }
-        println!("Created output directory: {}", cli.output_dir);
+        println!("Created output directory: {}", output_dir);
     }
     
+    let pipeline = Pipeline {
+        cli: &cli,
+        config,
+        info_flags,
+        redactor,
+        sanitizer,
+        output_dir: PathBuf::from(&output_dir),
+        format,
+    };
+    
     // Process a single file if specified
     if let Some(input_file) = &cli.input {
-        let input_path = PathBuf::from(&cli.input_dir).join(input_file);
+        let input_path = PathBuf::from(&input_dir).join(input_file);
         
         // Determine output filename
         let output_filename = match &cli.output {
             Some(output) => output.to_string(),
-            None => {
-                let base_name = Path::new(input_file)
-                    .file_stem()
-                    .and_then(|s| s.to_str())
-                    .unwrap_or("output");
-                format!("{}_openapi.yaml", base_name)
-            }
+            None => default_output_name(&input_path, pipeline.config.collection(&input_path), format),
         };
         
-        let output_path = PathBuf::from(&cli.output_dir).join(&output_filename);
+        let output_path = PathBuf::from(&output_dir).join(&output_filename);
         
-        // Convert the collection
-        println!("Processing: {}", input_path.display());
-        match parse_postman_collection(&input_path) {
-            Ok(postman_collection) => {
-                let openapi_spec = convert_postman_to_openapi(&postman_collection);
-                
-                match File::create(&output_path) {
-                    Ok(mut file) => {
-                        match serde_yaml::to_string(&openapi_spec) {
-                            Ok(yaml) => {
-                                if let Err(e) = file.write_all(yaml.as_bytes()) {
-                                    eprintln!("Failed to write to file: {}", e);
-                                    process::exit(1);
-                                }
-                                println!("Converted to {}", output_path.display());
-                            },
-                            Err(e) => {
-                                eprintln!("Failed to serialize to YAML: {}", e);
-                                process::exit(1);
-                            }
-                        }
-                    },
-                    Err(e) => {
-                        eprintln!("Failed to create output file: {}", e);
-                        process::exit(1);
-                    }
-                }
-            },
-            Err(e) => {
-                eprintln!("Error reading Postman collection: {}", e);
+        let converted = pipeline.convert_single(&input_path, &output_path);
+        if !cli.watch {
+            if !converted {
                 process::exit(1);
             }
+            return;
+        }
+        
+        let list = || vec![input_path.clone()];
+        let mut watcher = Watcher::new(&list());
+        println!("Watching {} for changes (Ctrl+C to stop)", input_path.display());
+        loop {
+            let changes = watcher.wait(list);
+            if changes.modified.is_empty() {
+                println!("\n{} was removed; waiting for it to come back", input_path.display());
+                continue;
+            }
+            println!("\nChange detected, regenerating");
+            pipeline.convert_single(&input_path, &output_path);
         }
     } 
     // Process all files in input directory
     else {
-        let input_path = Path::new(&cli.input_dir);
+        let input_path = Path::new(&input_dir);
         if !input_path.exists() {
-            eprintln!("Input directory '{}' does not exist. Please create it and add your Postman collections.", cli.input_dir);
+            eprintln!("Input directory '{}' does not exist. Please create it and add your Postman collections.", input_dir);
             process::exit(1);
         }
         
-        let mut files_processed = 0;
+        let filter = InputFilter::new(&cli, &pipeline.config, &pipeline.output_dir).unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            process::exit(1);
+        });
+        let list = || {
+            let mut inputs = Vec::new();
+            if let Err(e) = filter.collect(input_path, input_path, &mut inputs) {
+                eprintln!("Failed to read directory '{}': {}", input_dir, e);
+            }
+            inputs
+        };
+        let inputs = list();
         
-        match fs::read_dir(input_path) {
-            Ok(entries) => {
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                    if path.extension().and_then(|s| s.to_str()) == Some("json") {
-                        let base_name = path.file_stem()
-                            .and_then(|s| s.to_str())
-                            .unwrap_or("output");
-                        
-                        let output_path = PathBuf::from(&cli.output_dir)
-                            .join(format!("{}_openapi.yaml", base_name));
-                        
-                        println!("Processing: {}", path.display());
-                        match parse_postman_collection(&path) {
-                            Ok(postman_collection) => {
-                                let openapi_spec = convert_postman_to_openapi(&postman_collection);
-                                
-                                match File::create(&output_path) {
-                                    Ok(mut file) => {
-                                        match serde_yaml::to_string(&openapi_spec) {
-                                            Ok(yaml) => {
-                                                if let Err(e) = file.write_all(yaml.as_bytes()) {
-                                                    eprintln!("Failed to write to file: {}", e);
-                                                    continue;
-                                                }
-                                                println!("Converted to {}", output_path.display());
-                                                files_processed += 1;
-                                            },
-                                            Err(e) => {
-                                                eprintln!("Failed to serialize to YAML: {}", e);
-                                                continue;
-                                            }
-                                        }
-                                    },
-                                    Err(e) => {
-                                        eprintln!("Failed to create output file: {}", e);
-                                        continue;
-                                    }
-                                }
-                            },
-                            Err(e) => {
-                                eprintln!("Error reading Postman collection {}: {}", path.display(), e);
-                                continue;
-                            }
-                        }
-                    }
-                }
-            },
-            Err(e) => {
-                eprintln!("Failed to read directory '{}': {}", cli.input_dir, e);
+        // Zero lets rayon use one thread per core
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(cli.jobs.unwrap_or(0))
+            .build()
+            .unwrap_or_else(|e| {
+                eprintln!("Failed to start worker threads: {}", e);
                 process::exit(1);
-            }
+            });
+        
+        if inputs.is_empty() {
+            println!("No {} found in '{}'. Please add your input files to this directory.", cli.from.description(), input_dir);
+        } else if !pipeline.convert_batch(&pool, &inputs, input_path) && !cli.watch {
+            process::exit(1);
+        }
+        if !cli.watch {
+            return;
         }
         
-        if files_processed == 0 {
-            println!("No JSON files found in '{}'. Please add your Postman collections to this directory.", cli.input_dir);
-        } else {
-            println!("Processed {} collection(s).", files_processed);
+        let mut watcher = Watcher::new(&inputs);
+        println!("Watching '{}' for changes (Ctrl+C to stop)", input_dir);
+        loop {
+            let changes = watcher.wait(list);
+            println!();
+            for path in &changes.removed {
+                println!("Removed: {}", path.display());
+            }
+            // A merged spec depends on every input, so it is rebuilt whole
+            let affected: Vec<PathBuf> = if cli.merge {
+                list()
+            } else {
+                changes.modified.into_iter().collect()
+            };
+            if !affected.is_empty() {
+                pipeline.convert_batch(&pool, &affected, input_path);
+            }
         }
     }
 }
\ No newline at end of file
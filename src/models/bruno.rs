@@ -0,0 +1,258 @@
+use std::fs;
+use std::path::Path;
+use indexmap::IndexMap;
+use serde_json::Value;
+
+use crate::error::ConversionError;
+
+/// A Bruno collection: a directory tree of `.bru` request files with a
+/// `bruno.json` manifest at its root.
+#[derive(Debug)]
+pub struct BrunoCollection {
+    pub name: String,
+    pub root: BrunoFolder,
+}
+
+#[derive(Debug, Default)]
+pub struct BrunoFolder {
+    pub name: String,
+    pub requests: Vec<BruFile>,
+    pub folders: Vec<BrunoFolder>,
+}
+
+/// Contents of a single `.bru` file, block by block.
+#[derive(Debug, Default)]
+pub struct BruFile {
+    pub blocks: IndexMap<String, BruBlock>,
+}
+
+#[derive(Debug)]
+pub enum BruBlock {
+    /// `key: value` lines; disabled (`~key`) entries keep the flag
+    Dictionary(Vec<BruEntry>),
+    /// Free text such as `body:json` or `docs`, with indentation removed
+    Text(String),
+}
+
+#[derive(Debug)]
+pub struct BruEntry {
+    pub key: String,
+    pub value: String,
+    pub disabled: bool,
+}
+
+// Blocks whose content is free text rather than `key: value` pairs.
+const TEXT_BLOCKS: &[&str] = &[
+    "body:json",
+    "body:text",
+    "body:xml",
+    "body:sparql",
+    "body:graphql",
+    "body:graphql:vars",
+    "docs",
+    "script:pre-request",
+    "script:post-response",
+    "tests",
+];
+
+pub const HTTP_METHODS: &[&str] = &["get", "post", "put", "delete", "patch", "options", "head", "connect", "trace"];
+
+impl BruFile {
+    /// Parses Bru markup. Blocks open with `name {` at the start of a line
+    /// and close with a `}` at the start of a line.
+    pub fn parse_str(contents: &str) -> Result<Self, ConversionError> {
+        let mut blocks = IndexMap::new();
+        let mut lines = contents.lines().enumerate();
+
+        while let Some((number, line)) = lines.next() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            let name = match trimmed.strip_suffix('{') {
+                Some(name) if !line.starts_with(char::is_whitespace) => name.trim().to_string(),
+                _ => {
+                    return Err(ConversionError::InvalidFormat(
+                        format!("Unexpected content on line {}: {}", number + 1, trimmed)
+                    ));
+                }
+            };
+
+            let mut body = Vec::new();
+            let mut closed = false;
+            for (_, line) in lines.by_ref() {
+                if line.trim_end() == "}" {
+                    closed = true;
+                    break;
+                }
+                body.push(line);
+            }
+            if !closed {
+                return Err(ConversionError::InvalidFormat(
+                    format!("Block '{}' is not closed", name)
+                ));
+            }
+
+            let block = if TEXT_BLOCKS.contains(&name.as_str()) {
+                BruBlock::Text(dedent(&body))
+            } else {
+                BruBlock::Dictionary(parse_dictionary(&body))
+            };
+            blocks.insert(name, block);
+        }
+
+        Ok(BruFile { blocks })
+    }
+
+    pub fn parse(bru_file: &Path) -> Result<Self, ConversionError> {
+        let contents = fs::read_to_string(bru_file)
+            .map_err(ConversionError::FileError)?;
+
+        Self::parse_str(&contents).map_err(|e| match e {
+            ConversionError::InvalidFormat(msg) => {
+                ConversionError::InvalidFormat(format!("{}: {}", bru_file.display(), msg))
+            }
+            other => other,
+        })
+    }
+
+    pub fn dictionary(&self, name: &str) -> &[BruEntry] {
+        match self.blocks.get(name) {
+            Some(BruBlock::Dictionary(entries)) => entries,
+            _ => &[],
+        }
+    }
+
+    pub fn text(&self, name: &str) -> Option<&str> {
+        match self.blocks.get(name) {
+            Some(BruBlock::Text(text)) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Looks up an enabled value in a dictionary block.
+    pub fn value(&self, block: &str, key: &str) -> Option<&str> {
+        self.dictionary(block).iter()
+            .find(|entry| entry.key == key && !entry.disabled)
+            .map(|entry| entry.value.as_str())
+    }
+
+    /// The HTTP verb block (`get { ... }`, `post { ... }`) of a request file.
+    pub fn method(&self) -> Option<&str> {
+        self.blocks.keys()
+            .map(|name| name.as_str())
+            .find(|name| HTTP_METHODS.contains(name))
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.value("meta", "name")
+    }
+
+    pub fn seq(&self) -> u32 {
+        self.value("meta", "seq")
+            .and_then(|seq| seq.parse().ok())
+            .unwrap_or(u32::MAX)
+    }
+}
+
+fn dedent(lines: &[&str]) -> String {
+    let indent = lines.iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    lines.iter()
+        .map(|line| line.get(indent..).unwrap_or("").trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+fn parse_dictionary(lines: &[&str]) -> Vec<BruEntry> {
+    lines.iter()
+        .filter_map(|line| {
+            let line = line.trim();
+            let (key, value) = line.split_once(':')?;
+            let (key, disabled) = match key.strip_prefix('~') {
+                Some(key) => (key, true),
+                None => (key, false),
+            };
+            Some(BruEntry {
+                key: key.trim().to_string(),
+                value: value.trim().to_string(),
+                disabled,
+            })
+        })
+        .collect()
+}
+
+impl BrunoCollection {
+    pub fn parse(collection_dir: &Path) -> Result<Self, ConversionError> {
+        let manifest_path = collection_dir.join("bruno.json");
+        let manifest = fs::read_to_string(&manifest_path)
+            .map_err(ConversionError::FileError)?;
+        let manifest: Value = serde_json::from_str(&manifest)
+            .map_err(ConversionError::ParseError)?;
+
+        let name = manifest.get("name")
+            .and_then(|n| n.as_str())
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| dir_name(collection_dir));
+
+        let mut root = read_folder(collection_dir)?;
+        root.name = name.clone();
+
+        Ok(BrunoCollection { name, root })
+    }
+}
+
+fn dir_name(dir: &Path) -> String {
+    dir.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("")
+        .to_string()
+}
+
+fn read_folder(dir: &Path) -> Result<BrunoFolder, ConversionError> {
+    let mut folder = BrunoFolder {
+        name: dir_name(dir),
+        ..Default::default()
+    };
+
+    let mut entries: Vec<_> = fs::read_dir(dir)
+        .map_err(ConversionError::FileError)?
+        .flatten()
+        .map(|entry| entry.path())
+        .collect();
+    entries.sort();
+
+    for path in entries {
+        let file_name = dir_name(&path);
+        if path.is_dir() {
+            if file_name.starts_with('.') || file_name == "environments" || file_name == "node_modules" {
+                continue;
+            }
+            folder.folders.push(read_folder(&path)?);
+        } else if file_name == "folder.bru" {
+            if let Some(name) = BruFile::parse(&path)?.name() {
+                folder.name = name.to_string();
+            }
+        } else if path.extension().and_then(|e| e.to_str()) == Some("bru") && file_name != "collection.bru" {
+            let request = BruFile::parse(&path)?;
+            if request.method().is_some() {
+                folder.requests.push(request);
+            }
+        }
+    }
+
+    folder.requests.sort_by_key(|request| request.seq());
+
+    Ok(folder)
+}
+
+pub fn parse_bruno_collection(collection_dir: &Path) -> Result<BrunoCollection, ConversionError> {
+    BrunoCollection::parse(collection_dir)
+}
\ No newline at end of file
@@ -0,0 +1,401 @@
+use std::io::Read;
+use std::path::Path;
+use std::fs::File;
+
+use crate::error::ConversionError;
+
+/// A single `curl` invocation reduced to the parts that describe the request.
+#[derive(Debug, Default)]
+pub struct CurlCommand {
+    pub method: Option<String>,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    /// Payloads from `-d`, `--data`, `--data-raw`, `--data-binary` and `--json`
+    pub data: Vec<String>,
+    /// Decoded `name=value` pairs from `--data-urlencode`
+    pub urlencoded: Vec<(String, String)>,
+    /// `-F name=value` fields; the flag marks `name=@file` uploads
+    pub form: Vec<(String, String, bool)>,
+    /// Credentials from `-u user:password`
+    pub user: Option<String>,
+    /// `-G`: send the data as query parameters instead of a body
+    pub get: bool,
+    /// `--url-query` arguments, in the `--data-urlencode` syntax; a leading
+    /// `+` marks one that is already encoded
+    pub url_query: Vec<String>,
+    /// Files named by `-d @file` and friends, read by `parse`
+    pub data_files: Vec<String>,
+    /// What the parser skipped or couldn't do, such as unknown options
+    pub warnings: Vec<String>,
+}
+
+/// Short options taking an argument, which may be attached (`-XPOST`).
+const SHORT_WITH_ARGUMENT: &str = "AbcCdDeEFHKmoPQrtTuUwxXyYz";
+
+// Options we don't model but whose argument must be skipped.
+const OPTIONS_WITH_ARGUMENT: &[&str] = &[
+    "-o", "--output", "--output-dir", "-m", "--max-time", "--connect-timeout", "-w", "--write-out",
+    "-x", "--proxy", "-U", "--proxy-user", "--proxy-header", "--preproxy", "--noproxy",
+    "--retry", "--retry-delay", "--retry-max-time", "-c", "--cookie-jar", "-D", "--dump-header",
+    "--cacert", "--capath", "-E", "--cert", "--cert-type", "--key", "--key-type", "--pass",
+    "--ciphers", "--pinnedpubkey", "--crlfile", "-K", "--config", "-T", "--upload-file",
+    "-r", "--range", "-C", "--continue-at", "--resolve", "--connect-to", "--limit-rate",
+    "--interface", "--local-port", "--dns-servers", "--doh-url", "--unix-socket",
+    "--abstract-unix-socket", "--max-redirs", "--max-filesize", "-Y", "--speed-limit",
+    "-y", "--speed-time", "-z", "--time-cond", "--keepalive-time", "--expect100-timeout",
+    "--proto", "--proto-default", "--proto-redir", "--request-target", "--aws-sigv4",
+    "--socks4", "--socks4a", "--socks5", "--socks5-hostname", "--trace", "--trace-ascii",
+    "--stderr", "--libcurl", "--netrc-file", "--hsts", "--alt-svc", "--etag-compare",
+    "--etag-save", "-P", "--ftp-port", "-Q", "--quote", "-t", "--telnet-option",
+    "--tls-max", "--tlsuser", "--tlspassword", "--login-options", "--sasl-authzid",
+    "--service-name", "--delegation", "--variable", "--rate", "--parallel-max",
+];
+
+// Options without an argument, so a word after them is never theirs.
+const FLAGS: &[&str] = &[
+    "-s", "--silent", "-S", "--show-error", "-v", "--verbose", "-i", "--include",
+    "-k", "--insecure", "-L", "--location", "--location-trusted", "-f", "--fail",
+    "--fail-with-body", "--compressed", "-N", "--no-buffer", "-g", "--globoff",
+    "-#", "--progress-bar", "-n", "--netrc", "--netrc-optional", "-O", "--remote-name",
+    "-J", "--remote-header-name", "-0", "--http1.0", "--http1.1", "--http2",
+    "--http2-prior-knowledge", "--http3", "-4", "--ipv4", "-6", "--ipv6", "-q", "--disable",
+    "--raw", "--tr-encoding", "--path-as-is", "--digest", "--basic", "--ntlm", "--negotiate",
+    "--anyauth", "-l", "--list-only", "-a", "--append", "-B", "--use-ascii", "-j",
+    "--junk-session-cookies", "-p", "--proxytunnel", "-R", "--remote-time", "-Z", "--parallel",
+    "--create-dirs", "--no-keepalive", "--no-sessionid", "--ssl", "--ssl-reqd", "--tlsv1.2",
+    "--tlsv1.3", "--no-progress-meter", "--retry-all-errors", "--retry-connrefused",
+];
+
+/// Splits one argument into options with their attached values: `--data=x`
+/// into `--data` and `x`, and a cluster of short options such as `-sSXPOST`
+/// into `-s`, `-S` and `-X` with `POST`.
+fn split_options(arg: &str) -> Vec<(String, Option<String>)> {
+    if let Some(long) = arg.strip_prefix("--") {
+        return match long.split_once('=') {
+            Some((flag, value)) => vec![(format!("--{}", flag), Some(value.to_string()))],
+            None => vec![(arg.to_string(), None)],
+        };
+    }
+    let mut options = Vec::new();
+    for (index, c) in arg.char_indices().skip(1) {
+        if SHORT_WITH_ARGUMENT.contains(c) {
+            let rest = &arg[index + c.len_utf8()..];
+            options.push((format!("-{}", c), (!rest.is_empty()).then(|| rest.to_string())));
+            break;
+        }
+        options.push((format!("-{}", c), None));
+    }
+    options
+}
+
+/// Whether the word after an unknown option is more likely the URL than
+/// the option's argument.
+fn looks_like_url(word: &str) -> bool {
+    word.contains("://") || word.starts_with("{{") || word.starts_with("localhost")
+}
+
+impl CurlCommand {
+    pub fn parse(curl_file: &Path) -> Result<Vec<Self>, ConversionError> {
+        let mut file = File::open(curl_file)
+            .map_err(ConversionError::FileError)?;
+
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)
+            .map_err(ConversionError::FileError)?;
+
+        let mut commands = split_commands(&contents)
+            .iter()
+            .map(|command| Self::from_command_line(command))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // `@file` bodies are read relative to the file holding the commands
+        let base = curl_file.parent().unwrap_or(Path::new("."));
+        for command in &mut commands {
+            command.read_data_files(base);
+        }
+
+        if commands.is_empty() {
+            return Err(ConversionError::InvalidFormat(
+                "No curl commands found".to_string()
+            ));
+        }
+
+        Ok(commands)
+    }
+
+    /// Parses one complete `curl ...` command line.
+    pub fn from_command_line(line: &str) -> Result<Self, ConversionError> {
+        let tokens = tokenize(line)?;
+        let mut args = tokens.into_iter().skip(1).peekable();
+        let mut command = CurlCommand::default();
+
+        let missing = |flag: &str| ConversionError::InvalidFormat(format!("Missing value for {}", flag));
+
+        while let Some(arg) = args.next() {
+            if !arg.starts_with('-') || arg == "-" {
+                // Later URLs are extra requests; the first one describes the call
+                if command.url.is_empty() {
+                    command.url = arg;
+                }
+                continue;
+            }
+            let options = split_options(&arg);
+            let count = options.len();
+            for (index, (flag, inline)) in options.into_iter().enumerate() {
+                let mut value = |flag: &str| -> Result<String, ConversionError> {
+                    match &inline {
+                        Some(value) => Ok(value.clone()),
+                        None => args.next().ok_or_else(|| missing(flag)),
+                    }
+                };
+
+                match flag.as_str() {
+                    "-X" | "--request" => command.method = Some(value(&flag)?.to_uppercase()),
+                    "-H" | "--header" => {
+                        let header = value(&flag)?;
+                        if let Some((key, val)) = header.split_once(':') {
+                            command.headers.push((key.trim().to_string(), val.trim().to_string()));
+                        }
+                    }
+                    "-A" | "--user-agent" => command.headers.push(("User-Agent".to_string(), value(&flag)?)),
+                    "-e" | "--referer" => command.headers.push(("Referer".to_string(), value(&flag)?)),
+                    "-b" | "--cookie" => command.headers.push(("Cookie".to_string(), value(&flag)?)),
+                    "--oauth2-bearer" => {
+                        command.headers.push(("Authorization".to_string(), format!("Bearer {}", value(&flag)?)));
+                    }
+                    "--data-raw" => command.data.push(value(&flag)?),
+                    "-d" | "--data" | "--data-binary" | "--data-ascii" => command.push_data(value(&flag)?),
+                    "--json" => {
+                        command.push_data(value(&flag)?);
+                        if !command.has_header("content-type") {
+                            command.headers.push(("Content-Type".to_string(), "application/json".to_string()));
+                        }
+                    }
+                    "--data-urlencode" => command.urlencoded.push(split_urlencode(&value(&flag)?)),
+                    "-F" | "--form" | "--form-string" => {
+                        let field = value(&flag)?;
+                        if let Some((key, val)) = field.split_once('=') {
+                            let is_file = flag != "--form-string" && (val.starts_with('@') || val.starts_with('<'));
+                            command.form.push((key.to_string(), val.to_string(), is_file));
+                        }
+                    }
+                    "-u" | "--user" => command.user = Some(value(&flag)?),
+                    "-G" | "--get" => command.get = true,
+                    "--url-query" => command.url_query.push(value(&flag)?),
+                    "-I" | "--head" => command.method = Some("HEAD".to_string()),
+                    "--url" => {
+                        let url = value(&flag)?;
+                        if command.url.is_empty() {
+                            command.url = url;
+                        }
+                    }
+                    _ if OPTIONS_WITH_ARGUMENT.contains(&flag.as_str()) => {
+                        value(&flag)?;
+                    }
+                    _ if FLAGS.contains(&flag.as_str()) => {}
+                    _ => {
+                        // An unknown option may take an argument; only leave
+                        // the next word for the URL if it looks like one
+                        let last = index + 1 == count;
+                        let argument = if last && inline.is_none() {
+                            args.next_if(|next| !next.starts_with('-') && !looks_like_url(next))
+                        } else {
+                            inline.clone()
+                        };
+                        command.warnings.push(match argument {
+                            Some(argument) => format!("skipped unknown curl option {} {}", flag, argument),
+                            None => format!("skipped unknown curl option {}", flag),
+                        });
+                    }
+                }
+            }
+        }
+
+        if command.url.is_empty() {
+            return Err(ConversionError::InvalidFormat(
+                format!("No URL in curl command: {}", line)
+            ));
+        }
+
+        Ok(command)
+    }
+
+    /// Adds a `-d`-style payload, setting `@file` ones aside to be read.
+    fn push_data(&mut self, data: String) {
+        match data.strip_prefix('@') {
+            Some(file) => self.data_files.push(file.to_string()),
+            None => self.data.push(data),
+        }
+    }
+
+    /// Reads the `@file` payloads, relative to `base`. A file that can't be
+    /// read leaves the body out with a warning; the request stays a POST.
+    fn read_data_files(&mut self, base: &Path) {
+        for file in std::mem::take(&mut self.data_files) {
+            let read = match file.as_str() {
+                "-" => Err("standard input can't be read here".to_string()),
+                _ => std::fs::read_to_string(base.join(&file)).map_err(|e| e.to_string()),
+            };
+            match read {
+                Ok(contents) => self.data.push(contents.trim_end_matches(['\r', '\n']).to_string()),
+                Err(e) => {
+                    self.warnings.push(format!("left out the body of @{}: {}", file, e));
+                    if !self.get {
+                        self.method.get_or_insert_with(|| "POST".to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn has_header(&self, name: &str) -> bool {
+        self.headers.iter().any(|(key, _)| key.eq_ignore_ascii_case(name))
+    }
+
+    /// The method curl would use: `-X` wins, otherwise POST when a body is sent.
+    pub fn effective_method(&self) -> String {
+        if let Some(method) = &self.method {
+            return method.clone();
+        }
+        if !self.get && (!self.data.is_empty() || !self.urlencoded.is_empty() || !self.form.is_empty()) {
+            "POST".to_string()
+        } else {
+            "GET".to_string()
+        }
+    }
+}
+
+/// `--data-urlencode` accepts `content`, `=content`, `name=content`,
+/// `@file` and `name@file`; files can't be read here, so they keep the path.
+pub(crate) fn split_urlencode(value: &str) -> (String, String) {
+    match value.find(['=', '@']) {
+        Some(idx) if idx > 0 => (value[..idx].to_string(), value[idx + 1..].to_string()),
+        Some(_) => (String::new(), value[1..].to_string()),
+        None => (String::new(), value.to_string()),
+    }
+}
+
+/// Splits a file into logical commands: joins backslash (and PowerShell
+/// backtick) continuations, drops comments and anything that isn't a curl call.
+fn split_commands(contents: &str) -> Vec<String> {
+    let mut commands = Vec::new();
+    let mut current = String::new();
+
+    for line in contents.lines() {
+        let trimmed = line.trim_end();
+        if current.is_empty() && (trimmed.trim_start().starts_with('#') || trimmed.trim().is_empty()) {
+            continue;
+        }
+
+        match trimmed.strip_suffix('\\').or_else(|| trimmed.strip_suffix('`')) {
+            Some(continued) => {
+                current.push_str(continued);
+                current.push(' ');
+            }
+            None => {
+                current.push_str(trimmed);
+                let command = current.trim().trim_start_matches("$ ").to_string();
+                if command.starts_with("curl ") {
+                    commands.push(command);
+                }
+                current.clear();
+            }
+        }
+    }
+
+    let command = current.trim().to_string();
+    if command.starts_with("curl ") {
+        commands.push(command);
+    }
+
+    commands
+}
+
+/// POSIX-shell style word splitting with single, double and `$'...'` quoting.
+fn tokenize(line: &str) -> Result<Vec<String>, ConversionError> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_token = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if in_token {
+                    tokens.push(std::mem::take(&mut current));
+                    in_token = false;
+                }
+            }
+            '\'' => {
+                in_token = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => current.push(c),
+                        None => return Err(unterminated(line)),
+                    }
+                }
+            }
+            '"' => {
+                in_token = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => current.push(c),
+                            Some(c) => {
+                                current.push('\\');
+                                current.push(c);
+                            }
+                            None => return Err(unterminated(line)),
+                        },
+                        Some(c) => current.push(c),
+                        None => return Err(unterminated(line)),
+                    }
+                }
+            }
+            '$' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                in_token = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some('\\') => match chars.next() {
+                            Some('n') => current.push('\n'),
+                            Some('t') => current.push('\t'),
+                            Some('r') => current.push('\r'),
+                            Some(c) => current.push(c),
+                            None => return Err(unterminated(line)),
+                        },
+                        Some(c) => current.push(c),
+                        None => return Err(unterminated(line)),
+                    }
+                }
+            }
+            '\\' => {
+                in_token = true;
+                if let Some(c) = chars.next() {
+                    current.push(c);
+                }
+            }
+            c => {
+                in_token = true;
+                current.push(c);
+            }
+        }
+    }
+
+    if in_token {
+        tokens.push(current);
+    }
+
+    Ok(tokens)
+}
+
+fn unterminated(line: &str) -> ConversionError {
+    ConversionError::InvalidFormat(format!("Unterminated quote in curl command: {}", line))
+}
+
+pub fn parse_curl_commands(curl_file: &Path) -> Result<Vec<CurlCommand>, ConversionError> {
+    CurlCommand::parse(curl_file)
+}
\ No newline at end of file
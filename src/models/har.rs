@@ -0,0 +1,92 @@
+use std::io::Read;
+use std::path::Path;
+use std::fs::File;
+use serde::{Deserialize, Serialize};
+
+use crate::error::ConversionError;
+
+/// HTTP Archive (HAR 1.2) as exported by browser dev tools and proxies.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Har {
+    pub log: Log,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Log {
+    pub version: Option<String>,
+    pub pages: Option<Vec<Page>>,
+    pub entries: Vec<Entry>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Page {
+    pub id: Option<String>,
+    pub title: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Entry {
+    pub request: HarRequest,
+    pub response: HarResponse,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarRequest {
+    pub method: String,
+    pub url: String,
+    #[serde(default)]
+    pub headers: Vec<NameValue>,
+    #[serde(default)]
+    pub query_string: Vec<NameValue>,
+    pub post_data: Option<PostData>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarResponse {
+    pub status: u32,
+    pub status_text: Option<String>,
+    #[serde(default)]
+    pub headers: Vec<NameValue>,
+    pub content: Option<HarContent>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct NameValue {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostData {
+    pub mime_type: Option<String>,
+    pub text: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarContent {
+    pub mime_type: Option<String>,
+    pub text: Option<String>,
+    pub encoding: Option<String>,
+}
+
+impl Har {
+    pub fn parse(har_file: &Path) -> Result<Self, ConversionError> {
+        let mut file = File::open(har_file)
+            .map_err(ConversionError::FileError)?;
+
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)
+            .map_err(ConversionError::FileError)?;
+
+        serde_json::from_str(&contents)
+            .map_err(ConversionError::ParseError)
+    }
+}
+
+pub fn parse_har(har_file: &Path) -> Result<Har, ConversionError> {
+    Har::parse(har_file)
+}
\ No newline at end of file
@@ -1,2 +1,5 @@
 pub mod postman;
-pub mod openapi;
\ No newline at end of file
+pub mod openapi;
+pub mod har;
+pub mod curl;
+pub mod bruno;
\ No newline at end of file
@@ -1,16 +1,29 @@
 use indexmap::IndexMap;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
 use serde_json::Value;
 
+use crate::error::ConversionError;
+
+/// The operation keys of a path item, in the order OpenAPI lists them.
+pub const HTTP_METHODS: &[&str] = &["get", "put", "post", "delete", "options", "head", "patch", "trace"];
 
 #[derive(Debug, Serialize)]
 pub struct OpenAPISpec {
     pub openapi: String,
     pub info: OpenAPIInfo,
+    #[serde(rename = "externalDocs", skip_serializing_if = "Option::is_none")]
+    pub external_docs: Option<ExternalDocs>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub servers: Vec<Server>,
     pub paths: IndexMap<String, IndexMap<String, Operation>>,
     pub components: Components,
     pub tags: Vec<Tag>,
+    /// `x-` specification extensions
+    #[serde(flatten)]
+    pub extensions: IndexMap<String, Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -18,6 +31,50 @@ pub struct OpenAPIInfo {
     pub title: String,
     pub description: String,
     pub version: String,
+    #[serde(rename = "termsOfService", skip_serializing_if = "Option::is_none")]
+    pub terms_of_service: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact: Option<Contact>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<License>,
+    /// `x-` specification extensions
+    #[serde(flatten)]
+    pub extensions: IndexMap<String, Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Contact {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct License {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ExternalDocs {
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Server {
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -39,6 +96,9 @@ pub struct Operation {
     pub request_body: Option<RequestBody>,
     pub responses: IndexMap<String, OpenAPIResponse>,
     pub tags: Vec<String>,
+    /// `x-` specification extensions
+    #[serde(flatten)]
+    pub extensions: IndexMap<String, Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -58,11 +118,89 @@ pub struct Schema {
     #[serde(rename = "type")]
     pub schema_type: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub properties: Option<HashMap<String, Schema>>,
+    pub properties: Option<IndexMap<String, Schema>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub required: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub items: Option<Box<Schema>>,
+    /// Other keywords (`format`, `enum`, `nullable`, ...), which only
+    /// hand-written schemas have
+    #[serde(flatten)]
+    pub keywords: IndexMap<String, Value>,
+}
+
+impl Schema {
+    /// Reads a JSON Schema, such as one written in a Postman test script.
+    /// `type: ["string", "null"]` becomes `nullable` (written back as a type
+    /// array in 3.1 specs), and a missing type is taken from the keywords
+    /// that are present.
+    pub fn from_json_schema(value: &Value) -> Schema {
+        let Some(object) = value.as_object() else {
+            return Schema {
+                schema_type: "object".to_string(),
+                properties: None,
+                required: None,
+                items: None,
+                keywords: IndexMap::new(),
+            };
+        };
+
+        let mut keywords = IndexMap::new();
+        let mut types = Vec::new();
+        let mut properties = None;
+        let mut required = None;
+        let mut items = None;
+        for (key, keyword) in object {
+            match key.as_str() {
+                "type" => match keyword {
+                    Value::String(t) => types.push(t.clone()),
+                    Value::Array(list) => types.extend(list.iter().filter_map(|t| t.as_str()).map(|t| t.to_string())),
+                    _ => {}
+                },
+                "properties" => {
+                    properties = keyword.as_object().map(|p| {
+                        p.iter().map(|(name, property)| (name.clone(), Schema::from_json_schema(property))).collect()
+                    });
+                }
+                "required" => {
+                    required = keyword.as_array()
+                        .map(|r| r.iter().filter_map(|n| n.as_str()).map(|n| n.to_string()).collect());
+                }
+                "items" => items = Some(Box::new(Schema::from_json_schema(keyword))),
+                // Identifiers don't mean anything inside a spec
+                "$schema" | "$id" | "id" => {}
+                _ => {
+                    keywords.insert(key.clone(), keyword.clone());
+                }
+            }
+        }
+
+        // Draft 3 put `required: true` on the property itself
+        let flagged = object.get("properties").and_then(|p| p.as_object()).into_iter().flatten()
+            .filter(|(_, property)| property.get("required") == Some(&Value::Bool(true)));
+        for (name, _) in flagged {
+            let required = required.get_or_insert_with(Vec::new);
+            if !required.contains(name) {
+                required.push(name.clone());
+            }
+        }
+
+        if types.iter().any(|t| t == "null") {
+            types.retain(|t| t != "null");
+            keywords.insert("nullable".to_string(), Value::Bool(true));
+        }
+        let schema_type = match types.first() {
+            Some(t) => t.clone(),
+            None if properties.is_some() || required.is_some() => "object".to_string(),
+            None if items.is_some() => "array".to_string(),
+            None => match keywords.get("enum").and_then(|e| e.as_array()).and_then(|e| e.first()) {
+                Some(Value::Number(_)) => "number".to_string(),
+                Some(Value::Bool(_)) => "boolean".to_string(),
+                _ => "string".to_string(),
+            },
+        };
+        Schema { schema_type, properties, required, items, keywords }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -77,7 +215,7 @@ pub struct Content {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub example: Option<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub examples: Option<HashMap<String, Example>>,
+    pub examples: Option<IndexMap<String, Example>>,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -90,4 +228,26 @@ pub struct Example {
 pub struct OpenAPIResponse {
     pub description: String,
     pub content: HashMap<String, Content>,
+}
+
+/// Reads an existing OpenAPI document (YAML or JSON) as a generic value, so
+/// that fields the typed model doesn't know about survive a round trip.
+pub fn load_openapi_document(spec_file: &Path) -> Result<Value, ConversionError> {
+    let contents = fs::read_to_string(spec_file)
+        .map_err(ConversionError::FileError)?;
+
+    // Going through serde_yaml's own value lets unquoted status codes
+    // (`200:`) be read as map keys.
+    let document: serde_yaml::Value = serde_yaml::from_str(&contents)
+        .map_err(ConversionError::YamlError)?;
+    let document = serde_json::to_value(document)
+        .map_err(ConversionError::ParseError)?;
+
+    if !document.is_object() || document.get("openapi").is_none() {
+        return Err(ConversionError::InvalidFormat(
+            "Not an OpenAPI document".to_string()
+        ));
+    }
+
+    Ok(document)
 }
\ No newline at end of file
@@ -10,12 +10,53 @@ use crate::error::ConversionError;
 pub struct PostmanCollection {
     pub info: Option<Info>,
     pub item: Option<Vec<Item>>,
+    /// Scripts run around every request of the collection
+    pub event: Option<Vec<Event>>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Info {
     pub name: Option<String>,
     pub description: Option<String>,
+    pub version: Option<Version>,
+}
+
+/// Collection version, either a plain string or Postman's semver object.
+/// Any other shape (a bare number, an incomplete object) is kept as is so a
+/// malformed version never fails the whole collection.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Version {
+    Text(String),
+    Parts {
+        major: u64,
+        minor: u64,
+        patch: u64,
+        identifier: Option<String>,
+    },
+    Other(Value),
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Version::Text(version) => write!(f, "{}", version),
+            Version::Parts { major, minor, patch, identifier } => {
+                write!(f, "{}.{}.{}", major, minor, patch)?;
+                match identifier {
+                    Some(identifier) if !identifier.is_empty() => write!(f, "-{}", identifier),
+                    _ => Ok(()),
+                }
+            }
+            Version::Other(Value::Number(number)) => write!(f, "{}", number),
+            Version::Other(Value::Object(parts)) => {
+                // Fill in whichever of major, minor and patch are missing
+                let part = |key: &str| parts.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+                write!(f, "{}.{}.{}", part("major"), part("minor"), part("patch"))
+            }
+            Version::Other(_) => Ok(()),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -24,6 +65,60 @@ pub struct Item {
     pub item: Option<Vec<Item>>,
     pub request: Option<Request>,
     pub response: Option<Vec<Response>>,
+    pub event: Option<Vec<Event>>,
+}
+
+/// A script run before the request (`prerequest`) or after it (`test`).
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Event {
+    pub listen: Option<String>,
+    pub script: Option<Script>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Script {
+    #[serde(rename = "type")]
+    pub script_type: Option<String>,
+    pub exec: Option<Exec>,
+}
+
+/// Script source, exported as a list of lines or, by some tools, one string.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Exec {
+    Lines(Vec<String>),
+    Text(String),
+}
+
+impl Exec {
+    pub fn source(&self) -> String {
+        match self {
+            Exec::Lines(lines) => lines.join("\n"),
+            Exec::Text(text) => text.clone(),
+        }
+    }
+}
+
+/// Source of the scripts in `events` for one event (`prerequest` or `test`).
+pub fn event_script(events: Option<&[Event]>, listen: &str) -> Option<String> {
+    let sources: Vec<String> = events.into_iter().flatten()
+        .filter(|event| event.listen.as_deref() == Some(listen))
+        .filter_map(|event| event.script.as_ref()?.exec.as_ref())
+        .map(Exec::source)
+        .filter(|source| !source.trim().is_empty())
+        .collect();
+    if sources.is_empty() {
+        None
+    } else {
+        Some(sources.join("\n"))
+    }
+}
+
+impl Item {
+    /// Source of the item's scripts for one event (`prerequest` or `test`).
+    pub fn script(&self, listen: &str) -> Option<String> {
+        event_script(self.event.as_deref(), listen)
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -64,10 +159,43 @@ pub struct Header {
     pub disabled: Option<bool>,
 }
 
+impl Header {
+    pub fn new(key: &str, value: &str) -> Self {
+        Header {
+            key: Some(key.to_string()),
+            value: Some(value.to_string()),
+            description: None,
+            disabled: None,
+        }
+    }
+
+    /// Whether the header is called `name`, ignoring case.
+    pub fn is(&self, name: &str) -> bool {
+        self.key.as_deref().is_some_and(|key| key.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Value of the header called `name`, ignoring case.
+pub fn header_value<'a>(headers: &'a [Header], name: &str) -> Option<&'a str> {
+    headers.iter().find(|h| h.is(name)).and_then(|h| h.value.as_deref())
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Body {
     pub mode: Option<String>,
     pub raw: Option<String>,
+    pub urlencoded: Option<Vec<FormParameter>>,
+    pub formdata: Option<Vec<FormParameter>>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct FormParameter {
+    pub key: Option<String>,
+    pub value: Option<String>,
+    #[serde(rename = "type")]
+    pub param_type: Option<String>,
+    pub description: Option<String>,
+    pub disabled: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -76,9 +204,28 @@ pub struct Response {
     pub code: Option<u32>,
     pub header: Option<Vec<Header>>,
     pub body: Option<String>,
+    /// The request the response was saved for, e.g. with an unknown id for
+    /// a 404; ignored when it can't be read
+    #[serde(rename = "originalRequest", default, deserialize_with = "lenient")]
+    pub original_request: Option<Request>,
+}
+
+/// Reads an optional value, treating one of an unexpected shape as absent.
+fn lenient<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::de::DeserializeOwned,
+{
+    let value = Value::deserialize(deserializer)?;
+    Ok(serde_json::from_value(value).ok())
 }
 
 impl PostmanCollection {
+    /// Source of the collection-level scripts for one event.
+    pub fn script(&self, listen: &str) -> Option<String> {
+        event_script(self.event.as_deref(), listen)
+    }
+
     pub fn parse(postman_file: &Path) -> Result<Self, ConversionError> {
         let mut file = File::open(postman_file)
             .map_err(ConversionError::FileError)?;
@@ -0,0 +1,413 @@
+//! Language-neutral type model built from an OpenAPI document, which the
+//! emitters in the submodules turn into source code.
+
+pub mod rust;
+pub mod typescript;
+
+use std::collections::{HashMap, HashSet};
+use serde_json::Value;
+
+use crate::models::openapi::HTTP_METHODS;
+use crate::utils::examples::resolve;
+
+const MAX_DEPTH: usize = 12;
+
+/// Names the emitters use themselves, so generated types get numbered
+/// instead (`String` becomes `String2`): Rust std and serde types, and the
+/// TypeScript `Record` type and `Operations` interface.
+const RESERVED_NAMES: &[&str] = &[
+    "String", "Vec", "Option", "Box", "Value", "HashMap", "Result", "Self", "Serialize", "Deserialize", "Record",
+    "Operations",
+];
+
+/// The type of a field or payload.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeRef {
+    String,
+    Integer,
+    Number,
+    Boolean,
+    /// Anything, for mixed or unknown schemas
+    Any,
+    Array(Box<TypeRef>),
+    /// Object with arbitrary keys
+    Map(Box<TypeRef>),
+    Nullable(Box<TypeRef>),
+    /// A generated struct, enum or alias
+    Named(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct Field {
+    /// Name as it appears in the JSON
+    pub name: String,
+    pub type_ref: TypeRef,
+    pub required: bool,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum TypeDef {
+    Struct { name: String, fields: Vec<Field>, description: Option<String> },
+    /// String enum
+    Enum { name: String, values: Vec<String>, description: Option<String> },
+    Alias { name: String, target: TypeRef, description: Option<String> },
+}
+
+impl TypeDef {
+    pub fn name(&self) -> &str {
+        match self {
+            TypeDef::Struct { name, .. } | TypeDef::Enum { name, .. } | TypeDef::Alias { name, .. } => name,
+        }
+    }
+}
+
+/// Types of one operation's parameters and payloads.
+#[derive(Debug, Clone)]
+pub struct OperationTypes {
+    /// Upper-case HTTP method
+    pub method: String,
+    pub path: String,
+    /// PascalCase name derived from the operation
+    pub name: String,
+    pub path_params: Vec<Field>,
+    pub query_params: Vec<Field>,
+    pub request: Option<TypeRef>,
+    /// Status code and body type (`None` when the response has no body)
+    pub responses: Vec<(String, Option<TypeRef>)>,
+}
+
+#[derive(Debug, Default)]
+pub struct CodeModel {
+    pub title: String,
+    pub version: String,
+    pub types: Vec<TypeDef>,
+    pub operations: Vec<OperationTypes>,
+}
+
+/// `user_id`, `user-id` and `userId` all become `UserId`.
+pub fn pascal_case(text: &str) -> String {
+    let mut out = String::new();
+    for word in split_words(text) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            out.extend(first.to_uppercase());
+            out.push_str(&chars.as_str().to_lowercase());
+        }
+    }
+    out
+}
+
+/// PascalCase name that is a valid identifier, for types.
+pub fn type_name(text: &str) -> String {
+    let name = pascal_case(text);
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("T{}", name)
+    } else {
+        name
+    }
+}
+
+/// `userId`, `UserID` and `user-id` all become `user_id`.
+pub fn snake_case(text: &str) -> String {
+    split_words(text).iter().map(|word| word.to_lowercase()).collect::<Vec<_>>().join("_")
+}
+
+/// Splits on non-alphanumerics and lower-to-upper case changes, keeping
+/// acronyms together (`HTTPServer` → `HTTP`, `Server`).
+fn split_words(text: &str) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut words = Vec::new();
+    let mut current = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        let previous = if i > 0 { chars.get(i - 1) } else { None };
+        let next = chars.get(i + 1);
+        let boundary = c.is_uppercase() && previous.is_some_and(|p| {
+            p.is_lowercase() || p.is_ascii_digit()
+                || (p.is_uppercase() && next.is_some_and(|n| n.is_lowercase()))
+        });
+        if boundary && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn description(schema: &Value) -> Option<String> {
+    schema.get("description")
+        .and_then(|d| d.as_str())
+        .filter(|d| !d.is_empty())
+        .map(|d| d.to_string())
+}
+
+struct Builder<'a> {
+    root: &'a Value,
+    types: Vec<TypeDef>,
+    names: HashSet<String>,
+    /// Type name of each component schema, by its key in `components.schemas`
+    components: HashMap<String, String>,
+}
+
+impl Builder<'_> {
+    /// Reserves a unique type name, numbering repeats (`Item`, `Item2`, ...).
+    fn reserve(&mut self, name: &str) -> String {
+        let base = if name.is_empty() { "Type".to_string() } else { name.to_string() };
+        let mut candidate = base.clone();
+        let mut n = 2;
+        while !self.names.insert(candidate.clone()) {
+            candidate = format!("{}{}", base, n);
+            n += 1;
+        }
+        candidate
+    }
+
+    /// Pushes a placeholder so a parent type is listed before its nested types.
+    fn slot(&mut self, name: &str) -> usize {
+        self.types.push(TypeDef::Alias { name: name.to_string(), target: TypeRef::Any, description: None });
+        self.types.len() - 1
+    }
+
+    fn component_name(&self, reference: &str) -> Option<String> {
+        let key = reference.strip_prefix("#/components/schemas/")?.replace("~1", "/").replace("~0", "~");
+        Some(self.components.get(&key).cloned().unwrap_or_else(|| type_name(&key)))
+    }
+
+    /// Maps a schema to a type, defining structs and enums named `suggested`
+    /// for inline objects and enums.
+    fn type_for(&mut self, schema: &Value, suggested: &str, depth: usize) -> TypeRef {
+        if let Some(name) = schema.get("$ref").and_then(|r| r.as_str()).and_then(|r| self.component_name(r)) {
+            return TypeRef::Named(name);
+        }
+        let schema = resolve(schema, self.root);
+        if depth > MAX_DEPTH {
+            return TypeRef::Any;
+        }
+
+        let types: Vec<&str> = match schema.get("type") {
+            Some(Value::String(t)) => vec![t.as_str()],
+            Some(Value::Array(list)) => list.iter().filter_map(|t| t.as_str()).collect(),
+            _ => Vec::new(),
+        };
+        let nullable = types.contains(&"null") || schema.get("nullable").and_then(|n| n.as_bool()) == Some(true);
+        let non_null: Vec<&str> = types.iter().copied().filter(|t| *t != "null").collect();
+        let inner = match non_null.as_slice() {
+            _ if schema.get("oneOf").is_some() || schema.get("anyOf").is_some() => TypeRef::Any,
+            ["string"] if schema.get("enum").is_some() => self.define_enum(schema, suggested),
+            ["string"] => TypeRef::String,
+            ["integer"] => TypeRef::Integer,
+            ["number"] => TypeRef::Number,
+            ["boolean"] => TypeRef::Boolean,
+            ["array"] => {
+                let items = match schema.get("items") {
+                    Some(items) => self.type_for(items, &format!("{}Item", suggested), depth + 1),
+                    None => TypeRef::Any,
+                };
+                TypeRef::Array(Box::new(items))
+            }
+            ["object"] | [] if schema.get("properties").is_some() => self.define_struct(schema, suggested, depth),
+            ["object"] => match schema.get("additionalProperties") {
+                Some(values) if values.is_object() => {
+                    TypeRef::Map(Box::new(self.type_for(values, &format!("{}Value", suggested), depth + 1)))
+                }
+                _ => TypeRef::Map(Box::new(TypeRef::Any)),
+            },
+            _ => TypeRef::Any,
+        };
+        if nullable && inner != TypeRef::Any {
+            TypeRef::Nullable(Box::new(inner))
+        } else {
+            inner
+        }
+    }
+
+    fn define_enum(&mut self, schema: &Value, suggested: &str) -> TypeRef {
+        let values: Vec<String> = schema.get("enum").and_then(|e| e.as_array()).into_iter().flatten()
+            .filter_map(|v| v.as_str())
+            .map(|v| v.to_string())
+            .collect();
+        if values.is_empty() {
+            return TypeRef::String;
+        }
+        let name = self.reserve(suggested);
+        self.types.push(TypeDef::Enum { name: name.clone(), values, description: description(schema) });
+        TypeRef::Named(name)
+    }
+
+    fn define_struct(&mut self, schema: &Value, suggested: &str, depth: usize) -> TypeRef {
+        let name = self.reserve(suggested);
+        let index = self.slot(&name);
+        let required: Vec<&str> = schema.get("required").and_then(|r| r.as_array()).into_iter().flatten()
+            .filter_map(|v| v.as_str())
+            .collect();
+
+        let mut fields = Vec::new();
+        for (property, property_schema) in schema.get("properties").and_then(|p| p.as_object()).into_iter().flatten() {
+            // Nested types are named after their parent: `User` + `address`
+            let nested = format!("{}{}", name, pascal_case(property));
+            fields.push(Field {
+                name: property.clone(),
+                type_ref: self.type_for(property_schema, &nested, depth + 1),
+                required: required.contains(&property.as_str()),
+                description: description(property_schema),
+            });
+        }
+
+        self.types[index] = TypeDef::Struct { name: name.clone(), fields, description: description(schema) };
+        TypeRef::Named(name)
+    }
+
+    /// Gives a payload a type name of its own: an alias unless the schema
+    /// already became a named type.
+    fn payload(&mut self, schema: &Value, name: &str) -> TypeRef {
+        let type_ref = self.type_for(schema, name, 0);
+        match &type_ref {
+            TypeRef::Named(_) => type_ref,
+            _ => {
+                let alias = self.reserve(name);
+                self.types.push(TypeDef::Alias { name: alias.clone(), target: type_ref, description: None });
+                TypeRef::Named(alias)
+            }
+        }
+    }
+
+    fn parameters(&mut self, operation: &Value, location: &str, operation_name: &str) -> Vec<Field> {
+        operation.get("parameters").and_then(|p| p.as_array()).into_iter().flatten()
+            .map(|parameter| resolve(parameter, self.root))
+            .filter(|parameter| parameter.get("in").and_then(|i| i.as_str()) == Some(location))
+            .filter_map(|parameter| {
+                let name = parameter.get("name").and_then(|n| n.as_str())?;
+                let type_ref = match parameter.get("schema") {
+                    Some(schema) => self.type_for(schema, &format!("{}{}", operation_name, pascal_case(name)), 1),
+                    None => TypeRef::String,
+                };
+                Some(Field {
+                    name: name.to_string(),
+                    type_ref,
+                    required: parameter.get("required").and_then(|r| r.as_bool()).unwrap_or(false),
+                    description: description(parameter),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Picks the JSON body schema of a request body or response, if any.
+fn body_schema<'a>(holder: &'a Value, root: &'a Value) -> Option<&'a Value> {
+    let content = resolve(holder, root).get("content")?.as_object()?;
+    content.iter()
+        .find(|(content_type, _)| content_type.contains("json"))
+        .or_else(|| content.iter().next())
+        .and_then(|(_, media)| media.get("schema"))
+}
+
+/// Builds the type model: one type per component schema, then the inline
+/// request and response types of each operation, named after it
+/// (`GetUserResponse`, `GetUserResponse404`, `CreateUserRequest`).
+pub fn build_model(document: &Value) -> CodeModel {
+    let mut builder = Builder {
+        root: document,
+        types: Vec::new(),
+        names: RESERVED_NAMES.iter().map(|name| name.to_string()).collect(),
+        components: HashMap::new(),
+    };
+
+    let components: Vec<(&String, &Value)> = document.pointer("/components/schemas")
+        .and_then(|s| s.as_object())
+        .into_iter()
+        .flatten()
+        .collect();
+    // Reserve every component name first so inline types can't take them;
+    // `Node` and `node` become `Node` and `Node2`
+    for (key, _) in &components {
+        let name = builder.reserve(&type_name(key));
+        builder.components.insert(key.to_string(), name);
+    }
+    for (key, schema) in components {
+        let name = builder.components[key].clone();
+        let index = builder.slot(&name);
+        builder.names.remove(&name);
+        let type_ref = builder.type_for(schema, &name, 0);
+        match type_ref {
+            // Objects and enums were defined under the component's own name
+            TypeRef::Named(ref defined) if *defined == name => {
+                builder.types.remove(index);
+            }
+            target => {
+                builder.types[index] = TypeDef::Alias { name: name.clone(), target, description: description(schema) };
+            }
+        }
+        builder.names.insert(name);
+    }
+
+    let mut operations = Vec::new();
+    let mut operation_names = HashSet::new();
+    for (path, item) in document.get("paths").and_then(|p| p.as_object()).into_iter().flatten() {
+        for method in HTTP_METHODS {
+            let Some(operation) = item.get(*method) else { continue };
+            let label = operation.get("operationId").and_then(|o| o.as_str())
+                .or_else(|| operation.get("summary").and_then(|s| s.as_str()).filter(|s| !s.is_empty()))
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| format!("{} {}", method, path));
+            let mut name = pascal_case(&label);
+            if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+                name = format!("Op{}", name);
+            }
+            let mut unique = name.clone();
+            let mut n = 2;
+            while !operation_names.insert(unique.clone()) {
+                unique = format!("{}{}", name, n);
+                n += 1;
+            }
+            let name = unique;
+
+            let path_params = builder.parameters(operation, "path", &name);
+            let query_params = builder.parameters(operation, "query", &name);
+            let request = operation.get("requestBody")
+                .and_then(|body| body_schema(body, document))
+                .map(|schema| builder.payload(schema, &format!("{}Request", name)));
+
+            let mut responses = Vec::new();
+            let mut primary_named = false;
+            for (status, response) in operation.get("responses").and_then(|r| r.as_object()).into_iter().flatten() {
+                let type_ref = body_schema(response, document).map(|schema| {
+                    // The first success response is `XResponse`, the rest carry their status
+                    let type_name = if status.starts_with('2') && !primary_named {
+                        primary_named = true;
+                        format!("{}Response", name)
+                    } else {
+                        format!("{}Response{}", name, pascal_case(status))
+                    };
+                    builder.payload(schema, &type_name)
+                });
+                responses.push((status.clone(), type_ref));
+            }
+
+            operations.push(OperationTypes {
+                method: method.to_uppercase(),
+                path: path.clone(),
+                name,
+                path_params,
+                query_params,
+                request,
+                responses,
+            });
+        }
+    }
+
+    CodeModel {
+        title: document.pointer("/info/title").and_then(|t| t.as_str()).unwrap_or("API").to_string(),
+        version: document.pointer("/info/version").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+        types: builder.types,
+        operations,
+    }
+}
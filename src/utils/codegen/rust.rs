@@ -0,0 +1,186 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{pascal_case, snake_case, CodeModel, Field, TypeDef, TypeRef};
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern", "false", "fn", "for",
+    "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "static", "struct",
+    "trait", "true", "type", "unsafe", "use", "where", "while", "abstract", "become", "box", "do", "final",
+    "macro", "override", "priv", "try", "typeof", "unsized", "virtual", "yield",
+];
+// Keywords that can't be raw identifiers
+const RESERVED: &[&str] = &["self", "Self", "super", "crate"];
+
+fn field_ident(name: &str) -> String {
+    let ident = snake_case(name);
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("field_{}", ident)
+    } else if RESERVED.contains(&ident.as_str()) {
+        format!("{}_", ident)
+    } else if KEYWORDS.contains(&ident.as_str()) {
+        format!("r#{}", ident)
+    } else {
+        ident
+    }
+}
+
+fn variant_ident(value: &str) -> String {
+    let ident = pascal_case(value);
+    if ident.is_empty() {
+        "Empty".to_string()
+    } else if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("V{}", ident)
+    } else if RESERVED.contains(&ident.as_str()) {
+        format!("{}_", ident)
+    } else {
+        ident
+    }
+}
+
+/// Numbers repeated identifiers, e.g. when `userId` and `user_id` are both fields.
+fn unique(ident: String, separator: &str, used: &mut HashSet<String>) -> String {
+    let mut candidate = ident.clone();
+    let mut n = 2;
+    while !used.insert(candidate.clone()) {
+        candidate = format!("{}{}{}", ident, separator, n);
+        n += 1;
+    }
+    candidate
+}
+
+fn rust_type(type_ref: &TypeRef) -> String {
+    match type_ref {
+        TypeRef::String => "String".to_string(),
+        TypeRef::Integer => "i64".to_string(),
+        TypeRef::Number => "f64".to_string(),
+        TypeRef::Boolean => "bool".to_string(),
+        TypeRef::Any => "serde_json::Value".to_string(),
+        TypeRef::Array(items) => format!("Vec<{}>", rust_type(items)),
+        TypeRef::Map(values) => format!("std::collections::HashMap<String, {}>", rust_type(values)),
+        TypeRef::Nullable(inner) => format!("Option<{}>", rust_type(inner)),
+        TypeRef::Named(name) => name.clone(),
+    }
+}
+
+/// The named type stored inline by a value of `type_ref`; `Vec` and
+/// `HashMap` already put their items behind a pointer.
+fn inline_name(type_ref: &TypeRef) -> Option<&str> {
+    match type_ref {
+        TypeRef::Nullable(inner) => inline_name(inner),
+        TypeRef::Named(name) => Some(name),
+        _ => None,
+    }
+}
+
+/// For each type, the named types it stores inline.
+fn inline_edges(model: &CodeModel) -> HashMap<&str, Vec<&str>> {
+    model.types.iter()
+        .map(|def| {
+            let targets = match def {
+                TypeDef::Struct { fields, .. } => fields.iter().filter_map(|f| inline_name(&f.type_ref)).collect(),
+                TypeDef::Alias { target, .. } => inline_name(target).into_iter().collect(),
+                TypeDef::Enum { .. } => Vec::new(),
+            };
+            (def.name(), targets)
+        })
+        .collect()
+}
+
+/// Whether `to` is stored inline somewhere inside `from`.
+fn reaches(edges: &HashMap<&str, Vec<&str>>, from: &str, to: &str) -> bool {
+    let mut stack = vec![from];
+    let mut seen = HashSet::new();
+    while let Some(name) = stack.pop() {
+        if name == to {
+            return true;
+        }
+        if seen.insert(name) {
+            stack.extend(edges.get(name).into_iter().flatten().copied());
+        }
+    }
+    false
+}
+
+/// Type of a struct field: optional and nullable fields become a single
+/// `Option`, and a field whose type contains its owner, directly or through
+/// other types, is boxed.
+fn field_type(field: &Field, owner: &str, edges: &HashMap<&str, Vec<&str>>) -> (String, bool) {
+    let (inner, optional) = match &field.type_ref {
+        TypeRef::Nullable(inner) => (inner.as_ref(), true),
+        other => (other, !field.required),
+    };
+    let mut rendered = rust_type(inner);
+    if inline_name(inner).is_some_and(|name| reaches(edges, name, owner)) {
+        rendered = format!("Box<{}>", rendered);
+    }
+    if optional {
+        (format!("Option<{}>", rendered), true)
+    } else {
+        (rendered, false)
+    }
+}
+
+fn write_docs(out: &mut String, description: &Option<String>, indent: &str) {
+    if let Some(description) = description {
+        for line in description.lines() {
+            out.push_str(&format!("{}///{}{}\n", indent, if line.is_empty() { "" } else { " " }, line.trim_end()));
+        }
+    }
+}
+
+/// Renders the model as a Rust module of serde types.
+pub fn render(model: &CodeModel) -> String {
+    let mut out = format!(
+        "// Generated by postman-to-swagger from {} {}. Do not edit by hand.\n\nuse serde::{{Deserialize, Serialize}};\n",
+        model.title, model.version
+    );
+    let edges = inline_edges(model);
+
+    for def in &model.types {
+        out.push('\n');
+        match def {
+            TypeDef::Struct { name, fields, description } => {
+                write_docs(&mut out, description, "");
+                out.push_str("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n");
+                out.push_str(&format!("pub struct {} {{\n", name));
+                let mut used = HashSet::new();
+                for field in fields {
+                    let ident = unique(field_ident(&field.name), "_", &mut used);
+                    let (rendered, optional) = field_type(field, name, &edges);
+                    write_docs(&mut out, &field.description, "    ");
+                    let mut attributes = Vec::new();
+                    if ident.trim_start_matches("r#") != field.name {
+                        attributes.push(format!("rename = {:?}", field.name));
+                    }
+                    if optional {
+                        attributes.push("default, skip_serializing_if = \"Option::is_none\"".to_string());
+                    }
+                    if !attributes.is_empty() {
+                        out.push_str(&format!("    #[serde({})]\n", attributes.join(", ")));
+                    }
+                    out.push_str(&format!("    pub {}: {},\n", ident, rendered));
+                }
+                out.push_str("}\n");
+            }
+            TypeDef::Enum { name, values, description } => {
+                write_docs(&mut out, description, "");
+                out.push_str("#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]\n");
+                out.push_str(&format!("pub enum {} {{\n", name));
+                let mut used = HashSet::new();
+                for value in values {
+                    let ident = unique(variant_ident(value), "", &mut used);
+                    if ident != *value {
+                        out.push_str(&format!("    #[serde(rename = {:?})]\n", value));
+                    }
+                    out.push_str(&format!("    {},\n", ident));
+                }
+                out.push_str("}\n");
+            }
+            TypeDef::Alias { name, target, description } => {
+                write_docs(&mut out, description, "");
+                out.push_str(&format!("pub type {} = {};\n", name, rust_type(target)));
+            }
+        }
+    }
+    out
+}
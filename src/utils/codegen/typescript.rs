@@ -0,0 +1,145 @@
+use super::{CodeModel, Field, TypeDef, TypeRef};
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+/// Property key, quoted when it isn't a plain identifier (`"x-rate-limit"`).
+fn key(name: &str) -> String {
+    if is_identifier(name) {
+        name.to_string()
+    } else {
+        serde_json::to_string(name).unwrap_or_default()
+    }
+}
+
+fn ts_type(type_ref: &TypeRef) -> String {
+    match type_ref {
+        TypeRef::String => "string".to_string(),
+        TypeRef::Integer | TypeRef::Number => "number".to_string(),
+        TypeRef::Boolean => "boolean".to_string(),
+        TypeRef::Any => "unknown".to_string(),
+        TypeRef::Array(items) => match items.as_ref() {
+            TypeRef::Nullable(_) => format!("({})[]", ts_type(items)),
+            _ => format!("{}[]", ts_type(items)),
+        },
+        TypeRef::Map(values) => format!("Record<string, {}>", ts_type(values)),
+        TypeRef::Nullable(inner) => format!("{} | null", ts_type(inner)),
+        TypeRef::Named(name) => name.clone(),
+    }
+}
+
+fn write_docs(out: &mut String, description: &Option<String>, indent: &str) {
+    let Some(description) = description else { return };
+    let lines: Vec<&str> = description.lines().map(|line| line.trim_end()).collect();
+    if let [line] = lines.as_slice() {
+        out.push_str(&format!("{}/** {} */\n", indent, line.replace("*/", "*\\/")));
+        return;
+    }
+    out.push_str(&format!("{}/**\n", indent));
+    for line in lines {
+        out.push_str(&format!("{} *{}{}\n", indent, if line.is_empty() { "" } else { " " }, line.replace("*/", "*\\/")));
+    }
+    out.push_str(&format!("{} */\n", indent));
+}
+
+fn write_fields(out: &mut String, fields: &[Field], indent: &str) {
+    for field in fields {
+        write_docs(out, &field.description, indent);
+        out.push_str(&format!(
+            "{}{}{}: {};\n",
+            indent,
+            key(&field.name),
+            if field.required { "" } else { "?" },
+            ts_type(&field.type_ref)
+        ));
+    }
+}
+
+/// `GetPet` → `getPet`, the key of an operation in `Operations`.
+fn camel_case(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Renders the model as a TypeScript module: a declaration per type, then
+/// the `Operations` map typing every operation's method, path, parameters,
+/// body and responses by status, and an `operations` constant with the
+/// method and path of each for use at runtime.
+pub fn render(model: &CodeModel) -> String {
+    let mut out = format!("// Generated by postman-to-swagger from {} {}. Do not edit by hand.\n", model.title, model.version);
+
+    for def in &model.types {
+        out.push('\n');
+        match def {
+            TypeDef::Struct { name, fields, description } => {
+                write_docs(&mut out, description, "");
+                out.push_str(&format!("export interface {} {{\n", name));
+                write_fields(&mut out, fields, "  ");
+                out.push_str("}\n");
+            }
+            TypeDef::Enum { name, values, description } => {
+                write_docs(&mut out, description, "");
+                let members: Vec<String> = values.iter()
+                    .map(|value| serde_json::to_string(value).unwrap_or_default())
+                    .collect();
+                out.push_str(&format!("export type {} = {};\n", name, members.join(" | ")));
+            }
+            TypeDef::Alias { name, target, description } => {
+                write_docs(&mut out, description, "");
+                out.push_str(&format!("export type {} = {};\n", name, ts_type(target)));
+            }
+        }
+    }
+
+    out.push_str("\nexport interface Operations {\n");
+    for operation in &model.operations {
+        out.push_str(&format!("  {}: {{\n", key(&camel_case(&operation.name))));
+        out.push_str(&format!("    method: {:?};\n", operation.method));
+        out.push_str(&format!("    path: {};\n", serde_json::to_string(&operation.path).unwrap_or_default()));
+        let locations: Vec<(&str, &Vec<Field>)> = [("path", &operation.path_params), ("query", &operation.query_params)]
+            .into_iter()
+            .filter(|(_, params)| !params.is_empty())
+            .collect();
+        if locations.is_empty() {
+            out.push_str("    params: {};\n");
+        } else {
+            out.push_str("    params: {\n");
+        }
+        for (location, params) in &locations {
+            out.push_str(&format!("      {}: {{\n", location));
+            write_fields(&mut out, params, "        ");
+            out.push_str("      };\n");
+        }
+        if !locations.is_empty() {
+            out.push_str("    };\n");
+        }
+        if let Some(request) = &operation.request {
+            out.push_str(&format!("    body: {};\n", ts_type(request)));
+        }
+        out.push_str("    responses: {\n");
+        for (status, response) in &operation.responses {
+            let body = response.as_ref().map(ts_type).unwrap_or_else(|| "void".to_string());
+            out.push_str(&format!("      {}: {};\n", serde_json::to_string(status).unwrap_or_default(), body));
+        }
+        out.push_str("    };\n  };\n");
+    }
+    out.push_str("}\n");
+
+    out.push_str("\nexport const operations = {\n");
+    for operation in &model.operations {
+        out.push_str(&format!(
+            "  {}: {{ method: {:?}, path: {} }},\n",
+            key(&camel_case(&operation.name)),
+            operation.method,
+            serde_json::to_string(&operation.path).unwrap_or_default()
+        ));
+    }
+    out.push_str("} as const;\n");
+    out
+}
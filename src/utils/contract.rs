@@ -0,0 +1,428 @@
+use std::collections::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::converters::postman::{convert_postman_to_openapi, path_template};
+use crate::models::openapi::{Content, OpenAPISpec};
+use crate::models::postman::{header_value, Item, PostmanCollection, Request, Response};
+use crate::utils::path::percent_decode;
+use crate::utils::redact::PLACEHOLDER;
+
+/// Request headers the HTTP client sets itself.
+const SKIPPED_HEADERS: &[&str] = &["host", "content-length", "connection"];
+
+/// Contract tests for a collection: one case per saved response.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestPlan {
+    pub title: String,
+    /// Where the requests are sent; case paths are relative to it
+    pub base_url: String,
+    pub cases: Vec<TestCase>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TestCase {
+    /// Unique, usable as a test function name
+    pub name: String,
+    /// `METHOD /path/{template}` of the operation under test
+    pub operation: String,
+    pub request: CaseRequest,
+    pub expect: Expectation,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CaseRequest {
+    pub method: String,
+    /// Path with the saved query string, e.g. `/users/42?verbose=true`
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<(String, String)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Expectation {
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// Schema the JSON body must match, the one inferred for this
+    /// operation and status; `None` for bodies that aren't JSON
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<Value>,
+}
+
+/// The `{{name}}` variable or redacted placeholder `text` starts with.
+fn leading_token(text: &str) -> Option<&str> {
+    if text.starts_with(PLACEHOLDER) {
+        return Some(PLACEHOLDER);
+    }
+    let end = text.strip_prefix("{{")?.find("}}")? + 4;
+    Some(&text[..end])
+}
+
+/// Percent-encodes `text`, leaving variables and redacted placeholders as
+/// they are so the generated tests can fill them in.
+pub(crate) fn encode(text: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if let Some(token) = leading_token(rest) {
+            out.push_str(token);
+            rest = &rest[token.len()..];
+            continue;
+        }
+        let mut buffer = [0; 4];
+        for byte in c.encode_utf8(&mut buffer).bytes() {
+            match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => out.push(byte as char),
+                _ => out.push_str(&format!("%{:02X}", byte)),
+            }
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
+
+/// The concrete path a request was saved with, path variables filled in
+/// from the URL's `variable` list.
+fn concrete_path(request: &Request) -> String {
+    let Some(url) = &request.url else { return "/".to_string() };
+    let variables: HashMap<&str, &str> = url.variable.iter().flatten()
+        .filter_map(|v| Some((v.key.as_deref()?, v.value.as_deref()?)))
+        .collect();
+    let segments: Vec<String> = url.path.iter().flatten()
+        .filter_map(|segment| match segment {
+            Value::String(s) => Some(match s.strip_prefix(':').and_then(|name| variables.get(name)) {
+                Some(value) => value.to_string(),
+                None => s.clone(),
+            }),
+            Value::Object(obj) => obj.get("value").and_then(|v| v.as_str()).map(|name| {
+                variables.get(name).map(|value| value.to_string()).unwrap_or_else(|| format!(":{}", name))
+            }),
+            _ => None,
+        })
+        .collect();
+    let mut path = format!("/{}", segments.join("/").trim_start_matches('/'));
+
+    let query: Vec<String> = url.query.iter().flatten()
+        .filter(|q| !q.disabled.unwrap_or(false))
+        .filter_map(|q| {
+            let key = q.key.as_deref()?;
+            Some(format!("{}={}", encode(&percent_decode(key)), encode(&percent_decode(q.value.as_deref().unwrap_or("")))))
+        })
+        .collect();
+    if !query.is_empty() {
+        path.push('?');
+        path.push_str(&query.join("&"));
+    }
+    path
+}
+
+fn request_body(request: &Request) -> Option<String> {
+    let body = request.body.as_ref()?;
+    match body.mode.as_deref() {
+        Some("raw") => body.raw.clone().filter(|raw| !raw.is_empty()),
+        Some("urlencoded") | Some("formdata") => {
+            let fields = body.urlencoded.as_ref().or(body.formdata.as_ref())?;
+            let pairs: Vec<String> = fields.iter()
+                .filter(|f| !f.disabled.unwrap_or(false))
+                .filter_map(|f| Some(format!("{}={}", encode(f.key.as_deref()?), encode(f.value.as_deref().unwrap_or("")))))
+                .collect();
+            Some(pairs.join("&"))
+        }
+        _ => None,
+    }
+}
+
+pub(crate) fn case_request(request: &Request, method: &str) -> CaseRequest {
+    let mut headers: Vec<(String, String)> = request.header.iter().flatten()
+        .filter(|h| !h.disabled.unwrap_or(false))
+        .filter_map(|h| Some((h.key.clone()?, h.value.clone().unwrap_or_default())))
+        .filter(|(name, _)| !SKIPPED_HEADERS.contains(&name.to_lowercase().as_str()))
+        .collect();
+    let body = request_body(request);
+    // Form bodies are sent url-encoded
+    let form = matches!(request.body.as_ref().and_then(|b| b.mode.as_deref()), Some("urlencoded") | Some("formdata"));
+    if form {
+        headers.retain(|(name, _)| !name.eq_ignore_ascii_case("content-type"));
+        headers.push(("Content-Type".to_string(), "application/x-www-form-urlencoded".to_string()));
+    }
+    CaseRequest { method: method.to_string(), path: concrete_path(request), headers, body }
+}
+
+/// The JSON body schema converted for an operation and status.
+fn expected_schema(spec: &OpenAPISpec, path: &str, method: &str, status: &str) -> Option<Value> {
+    let response = spec.paths.get(path)?.get(&method.to_lowercase())?.responses.get(status)?;
+    response.content.iter()
+        .find(|(content_type, _)| content_type.contains("json"))
+        .map(|(_, Content { schema, .. })| serde_json::to_value(schema).unwrap_or(Value::Null))
+}
+
+fn case_name(text: &str) -> String {
+    let mut name = String::new();
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            name.push(c.to_ascii_lowercase());
+        } else if !name.is_empty() && !name.ends_with('_') {
+            name.push('_');
+        }
+    }
+    let name = name.trim_end_matches('_').to_string();
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("case_{}", name)
+    } else {
+        name
+    }
+}
+
+struct PlanBuilder<'a> {
+    spec: &'a OpenAPISpec,
+    names: HashSet<String>,
+    cases: Vec<TestCase>,
+}
+
+impl PlanBuilder<'_> {
+    fn add(&mut self, item: &Item, request: &Request, response: &Response) {
+        let method = request.method.as_deref().unwrap_or("GET").to_uppercase();
+        let template = request.url.as_ref().map(path_template).unwrap_or_else(|| "/".to_string());
+        let status = response.code.unwrap_or(200) as u16;
+
+        let base = case_name(&format!("{} {}", item.name.as_deref().unwrap_or(&method), status));
+        let mut name = base.clone();
+        let mut n = 2;
+        while !self.names.insert(name.clone()) {
+            name = format!("{}_{}", base, n);
+            n += 1;
+        }
+
+        // Replay the request the response was saved for when there is one
+        let mut case = case_request(response.original_request.as_ref().unwrap_or(request), &method);
+        if !(200..300).contains(&status) {
+            // Mock servers answer with the first success response unless asked otherwise
+            case.headers.push(("Prefer".to_string(), format!("code={}", status)));
+        }
+
+        let content_type = header_value(response.header.as_deref().unwrap_or_default(), "content-type").map(|c| c.to_string());
+        let json = content_type.as_deref().is_none_or(|c| c.contains("json"));
+        self.cases.push(TestCase {
+            name,
+            operation: format!("{} {}", method, template),
+            request: case,
+            expect: Expectation {
+                status,
+                schema: if json { expected_schema(self.spec, &template, &method, &status.to_string()) } else { None },
+                content_type,
+            },
+        });
+    }
+
+    fn walk(&mut self, items: &[Item]) {
+        for item in items {
+            if let Some(children) = &item.item {
+                self.walk(children);
+                continue;
+            }
+            let Some(request) = &item.request else { continue };
+            for response in item.response.iter().flatten() {
+                self.add(item, request, response);
+            }
+        }
+    }
+}
+
+/// Builds contract tests from the saved responses of a collection: each
+/// response becomes a case sending the request it was saved for (the
+/// item's, unless the response has its own) and expecting the saved status
+/// and a body matching the schema inferred for it.
+pub fn build_plan(collection: &PostmanCollection, base_url: &str) -> TestPlan {
+    let spec = convert_postman_to_openapi(collection);
+    let mut builder = PlanBuilder { spec: &spec, names: HashSet::new(), cases: Vec::new() };
+    builder.walk(collection.item.as_deref().unwrap_or_default());
+    TestPlan {
+        title: spec.info.title.clone(),
+        base_url: base_url.trim_end_matches('/').to_string(),
+        cases: builder.cases,
+    }
+}
+
+// Shape check compiled into the generated tests, the same rules as
+// `validate_instance` for the schemas the converter infers.
+const RUST_HELPERS: &str = r#"fn base_url() -> String {
+    std::env::var("CONTRACT_BASE_URL").unwrap_or_else(|_| BASE_URL.to_string())
+}
+
+fn env(name: &str) -> String {
+    std::env::var(name).unwrap_or_else(|_| panic!("set {} to run this test", name))
+}
+
+fn send(method: &str, path: &str, headers: &[(&str, &str)], body: Option<&str>) -> (u16, String) {
+    let url = format!("{}{}", base_url().trim_end_matches('/'), path);
+    let mut request = ureq::request(method, &url);
+    for (name, value) in headers {
+        request = request.set(name, value);
+    }
+    let result = match body {
+        Some(body) => request.send_string(body),
+        None => request.call(),
+    };
+    match result {
+        Ok(response) | Err(ureq::Error::Status(_, response)) => {
+            let status = response.status();
+            (status, response.into_string().unwrap_or_default())
+        }
+        Err(e) => panic!("{} {} failed: {}", method, url, e),
+    }
+}
+
+fn check_shape(value: &Value, schema: &Value, at: &str, problems: &mut Vec<String>) {
+    let expected = schema["type"].as_str().unwrap_or("");
+    let matches = match expected {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        _ => true,
+    };
+    if !matches {
+        problems.push(format!("{}: expected {}, found {}", at, expected, value));
+        return;
+    }
+    if let Some(object) = value.as_object() {
+        let required: Vec<&str> = schema["required"].as_array().into_iter().flatten().filter_map(|r| r.as_str()).collect();
+        for name in &required {
+            if !object.contains_key(*name) {
+                problems.push(format!("{}: missing required property '{}'", at, name));
+            }
+        }
+        for (name, property) in schema["properties"].as_object().into_iter().flatten() {
+            match object.get(name) {
+                Some(Value::Null) if !required.contains(&name.as_str()) => {}
+                Some(child) => check_shape(child, property, &format!("{}.{}", at, name), problems),
+                None => {}
+            }
+        }
+    }
+    if let (Some(items), Some(item_schema)) = (value.as_array(), schema.get("items")) {
+        for (i, item) in items.iter().enumerate() {
+            check_shape(item, item_schema, &format!("{}[{}]", at, i), problems);
+        }
+    }
+}
+"#;
+
+/// A raw string literal for `text`, with enough `#`s to hold it.
+fn raw_string(text: &str) -> String {
+    let mut hashes = 1;
+    while text.contains(&format!("\"{}", "#".repeat(hashes))) {
+        hashes += 1;
+    }
+    let hashes = "#".repeat(hashes);
+    format!("r{}\"{}\"{}", hashes, text, hashes)
+}
+
+/// `CONTRACT_USER_ID` for `userId`.
+pub(crate) fn env_name(name: &str) -> String {
+    let name: String = name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect();
+    format!("CONTRACT_{}", name.trim_matches('_'))
+}
+
+/// The query, form or JSON key a value at the end of `text` belongs to:
+/// `token` for `?token=` and `"token": "`.
+fn key_before(text: &str) -> Option<&str> {
+    let text = text.trim_end_matches([' ', '"']);
+    let text = text.strip_suffix('=').or_else(|| text.strip_suffix(':'))?.trim_end_matches([' ', '"']);
+    let start = text.char_indices().rev()
+        .find(|(_, c)| !(c.is_alphanumeric() || *c == '_' || *c == '-'))
+        .map_or(0, |(i, c)| i + c.len_utf8());
+    Some(&text[start..]).filter(|key| !key.is_empty())
+}
+
+/// A `&str` expression for `text`, written with `literal`, that reads each
+/// `{{name}}` variable from `CONTRACT_NAME` and each redacted value from an
+/// environment variable named after its key, or after `secret`.
+fn rust_str(text: &str, secret: &str, literal: fn(&str) -> String) -> String {
+    let mut template = String::new();
+    let mut lookups = Vec::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        match leading_token(rest) {
+            Some(token) => {
+                let name = match token.strip_prefix("{{").and_then(|t| t.strip_suffix("}}")) {
+                    Some(variable) => env_name(variable),
+                    None => env_name(key_before(&text[..text.len() - rest.len()]).unwrap_or(secret)),
+                };
+                template.push_str("{}");
+                lookups.push(format!("env({:?})", name));
+                rest = &rest[token.len()..];
+            }
+            None => {
+                match c {
+                    '{' => template.push_str("{{"),
+                    '}' => template.push_str("}}"),
+                    _ => template.push(c),
+                }
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    if lookups.is_empty() {
+        literal(text)
+    } else if template == "{}" {
+        format!("{}.as_str()", lookups[0])
+    } else {
+        format!("format!({}, {}).as_str()", literal(&template), lookups.join(", "))
+    }
+}
+
+fn debug_string(text: &str) -> String {
+    format!("{:?}", text)
+}
+
+/// Renders the plan as a Rust integration test file, one `#[test]` per case.
+/// The tests use `ureq` and `serde_json`, and send requests to
+/// `CONTRACT_BASE_URL` when it is set, otherwise to the plan's base URL.
+/// Postman variables and redacted secrets are read from `CONTRACT_*`
+/// environment variables when the test runs.
+pub fn render_rust_tests(plan: &TestPlan) -> String {
+    let mut out = format!(
+        "//! Contract tests generated by postman-to-swagger from {}. Do not edit by hand.\n\
+         //! Needs `ureq` 2 and `serde_json` as dev-dependencies; set `CONTRACT_BASE_URL`\n\
+         //! to test another server. Postman variables and redacted secrets are read from\n\
+         //! `CONTRACT_<NAME>` environment variables, e.g. `CONTRACT_AUTHORIZATION`.\n\n\
+         use serde_json::Value;\n\n\
+         const BASE_URL: &str = {:?};\n\n{}",
+        plan.title, plan.base_url, RUST_HELPERS
+    );
+    for case in &plan.cases {
+        let headers: Vec<String> = case.request.headers.iter()
+            .map(|(name, value)| format!("({:?}, {})", name, rust_str(value, name, debug_string)))
+            .collect();
+        let body = match &case.request.body {
+            Some(body) => format!("Some({})", rust_str(body, "secret", raw_string)),
+            None => "None".to_string(),
+        };
+        out.push_str(&format!("\n/// {}\n#[test]\nfn {}() {{\n", case.operation, case.name));
+        out.push_str(&format!(
+            "    let (status, body) = send({:?}, {}, &[{}], {});\n",
+            case.request.method, rust_str(&case.request.path, "secret", debug_string), headers.join(", "), body
+        ));
+        out.push_str(&format!("    assert_eq!(status, {}, \"unexpected status, body: {{}}\", body);\n", case.expect.status));
+        if let Some(schema) = &case.expect.schema {
+            let schema = serde_json::to_string(schema).unwrap_or_default();
+            out.push_str("    let body: Value = serde_json::from_str(&body).expect(\"response body is not JSON\");\n");
+            out.push_str(&format!("    let schema: Value = serde_json::from_str({}).unwrap();\n", raw_string(&schema)));
+            out.push_str("    let mut problems = Vec::new();\n");
+            out.push_str("    check_shape(&body, &schema, \"$\", &mut problems);\n");
+            out.push_str("    assert!(problems.is_empty(), \"{}\", problems.join(\"\\n\"));\n");
+        }
+        out.push_str("}\n");
+    }
+    out
+}
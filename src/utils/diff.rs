@@ -0,0 +1,347 @@
+use std::collections::BTreeSet;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::models::openapi::HTTP_METHODS;
+use crate::utils::examples::resolve;
+
+/// A single difference between two specs, from an API consumer's view.
+#[derive(Debug, Clone, Serialize)]
+pub struct Change {
+    pub breaking: bool,
+    /// The affected operation (`GET /users`) or path
+    pub location: String,
+    pub message: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct SpecDiff {
+    pub changes: Vec<Change>,
+}
+
+// Stops recursion into deeply nested or recursive schemas.
+const MAX_DEPTH: usize = 32;
+
+/// Which way data flows through a schema. Making a request schema stricter
+/// breaks clients; making a response schema looser does.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Request,
+    Response,
+}
+
+struct Differ<'a> {
+    old_root: &'a Value,
+    new_root: &'a Value,
+    changes: Vec<Change>,
+}
+
+impl SpecDiff {
+    pub fn has_breaking(&self) -> bool {
+        self.changes.iter().any(|c| c.breaking)
+    }
+
+    fn counts(&self) -> (usize, usize) {
+        let breaking = self.changes.iter().filter(|c| c.breaking).count();
+        (breaking, self.changes.len() - breaking)
+    }
+
+    pub fn to_text(&self) -> String {
+        if self.changes.is_empty() {
+            return "No changes.\n".to_string();
+        }
+        let mut out = String::new();
+        for change in &self.changes {
+            let label = if change.breaking { "BREAKING" } else { "change  " };
+            out.push_str(&format!("{} {}: {}\n", label, change.location, change.message));
+        }
+        let (breaking, other) = self.counts();
+        out.push_str(&format!("\n{} breaking, {} non-breaking change(s)\n", breaking, other));
+        out
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let (breaking, other) = self.counts();
+        let mut out = String::from("# API Changes\n\n");
+        out.push_str(&format!("{} breaking, {} non-breaking change(s)\n", breaking, other));
+
+        for (title, is_breaking) in [("Breaking changes", true), ("Non-breaking changes", false)] {
+            let changes: Vec<&Change> = self.changes.iter().filter(|c| c.breaking == is_breaking).collect();
+            if changes.is_empty() {
+                continue;
+            }
+            out.push_str(&format!("\n## {}\n\n", title));
+            for change in changes {
+                out.push_str(&format!("- `{}`: {}\n", change.location, change.message));
+            }
+        }
+        out
+    }
+
+    pub fn to_json(&self) -> String {
+        let (breaking, other) = self.counts();
+        let report = serde_json::json!({
+            "breaking": breaking,
+            "nonBreaking": other,
+            "changes": self.changes,
+        });
+        serde_json::to_string_pretty(&report).unwrap_or_default()
+    }
+}
+
+fn keys(value: Option<&Value>) -> BTreeSet<String> {
+    value.and_then(|v| v.as_object())
+        .map(|o| o.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+fn string_list(value: Option<&Value>) -> BTreeSet<String> {
+    value.and_then(|v| v.as_array())
+        .map(|list| list.iter().filter_map(|s| s.as_str().map(|s| s.to_string())).collect())
+        .unwrap_or_default()
+}
+
+fn schema_type(schema: &Value) -> Option<String> {
+    match schema.get("type") {
+        Some(Value::String(t)) => Some(t.clone()),
+        Some(Value::Array(list)) => Some(list.iter().filter_map(|t| t.as_str()).collect::<Vec<_>>().join("|")),
+        _ => None,
+    }
+}
+
+fn parameters<'v>(path_item: &'v Value, operation: &'v Value) -> Vec<&'v Value> {
+    let mut list: Vec<&Value> = operation.get("parameters").and_then(|p| p.as_array()).into_iter().flatten().collect();
+    for parameter in path_item.get("parameters").and_then(|p| p.as_array()).into_iter().flatten() {
+        let overridden = list.iter().any(|p| p.get("name") == parameter.get("name") && p.get("in") == parameter.get("in"));
+        if !overridden {
+            list.push(parameter);
+        }
+    }
+    list
+}
+
+fn is_required(value: &Value) -> bool {
+    value.get("required") == Some(&Value::Bool(true))
+}
+
+impl Differ<'_> {
+    fn record(&mut self, breaking: bool, location: &str, message: String) {
+        self.changes.push(Change {
+            breaking,
+            location: location.to_string(),
+            message,
+        });
+    }
+
+    fn paths(&mut self, old: &Value, new: &Value) {
+        let old_paths = old.get("paths");
+        let new_paths = new.get("paths");
+
+        for path in keys(old_paths).union(&keys(new_paths)) {
+            let old_item = old_paths.and_then(|p| p.get(path));
+            let new_item = new_paths.and_then(|p| p.get(path));
+            match (old_item, new_item) {
+                (Some(_), None) => self.record(true, path, "path removed".to_string()),
+                (None, Some(_)) => self.record(false, path, "path added".to_string()),
+                (Some(old_item), Some(new_item)) => {
+                    for method in HTTP_METHODS {
+                        let location = format!("{} {}", method.to_uppercase(), path);
+                        match (old_item.get(*method), new_item.get(*method)) {
+                            (Some(_), None) => self.record(true, &location, "operation removed".to_string()),
+                            (None, Some(_)) => self.record(false, &location, "operation added".to_string()),
+                            (Some(old_op), Some(new_op)) => {
+                                self.operation(&location, old_item, old_op, new_item, new_op);
+                            }
+                            (None, None) => {}
+                        }
+                    }
+                }
+                (None, None) => {}
+            }
+        }
+    }
+
+    fn operation(&mut self, location: &str, old_item: &Value, old_op: &Value, new_item: &Value, new_op: &Value) {
+        if !old_op.get("deprecated").and_then(|d| d.as_bool()).unwrap_or(false)
+            && new_op.get("deprecated").and_then(|d| d.as_bool()).unwrap_or(false)
+        {
+            self.record(false, location, "operation deprecated".to_string());
+        }
+
+        let old_params = parameters(old_item, old_op);
+        let new_params = parameters(new_item, new_op);
+        let key = |p: &Value| (p.get("name").cloned(), p.get("in").cloned());
+
+        for old_param in &old_params {
+            let name = old_param.get("name").and_then(|n| n.as_str()).unwrap_or("");
+            let location_in = old_param.get("in").and_then(|n| n.as_str()).unwrap_or("");
+            match new_params.iter().find(|p| key(p) == key(old_param)) {
+                None => self.record(false, location, format!("{} parameter '{}' removed", location_in, name)),
+                Some(new_param) => {
+                    if !is_required(old_param) && is_required(new_param) {
+                        self.record(true, location, format!("{} parameter '{}' became required", location_in, name));
+                    } else if is_required(old_param) && !is_required(new_param) {
+                        self.record(false, location, format!("{} parameter '{}' became optional", location_in, name));
+                    }
+                    if let (Some(old_schema), Some(new_schema)) = (old_param.get("schema"), new_param.get("schema")) {
+                        let what = format!("{} parameter '{}'", location_in, name);
+                        self.schema(location, &what, old_schema, new_schema, Direction::Request, 0);
+                    }
+                }
+            }
+        }
+        for new_param in &new_params {
+            if !old_params.iter().any(|p| key(p) == key(new_param)) {
+                let name = new_param.get("name").and_then(|n| n.as_str()).unwrap_or("");
+                let location_in = new_param.get("in").and_then(|n| n.as_str()).unwrap_or("");
+                if is_required(new_param) {
+                    self.record(true, location, format!("new required {} parameter '{}'", location_in, name));
+                } else {
+                    self.record(false, location, format!("new optional {} parameter '{}'", location_in, name));
+                }
+            }
+        }
+
+        match (old_op.get("requestBody"), new_op.get("requestBody")) {
+            (None, Some(body)) if is_required(body) => {
+                self.record(true, location, "new required request body".to_string());
+            }
+            (None, Some(_)) => self.record(false, location, "new optional request body".to_string()),
+            (Some(_), None) => self.record(false, location, "request body removed".to_string()),
+            (Some(old_body), Some(new_body)) => {
+                if !is_required(old_body) && is_required(new_body) {
+                    self.record(true, location, "request body became required".to_string());
+                }
+                self.content(location, "request body", old_body.get("content"), new_body.get("content"), Direction::Request);
+            }
+            (None, None) => {}
+        }
+
+        let old_responses = old_op.get("responses");
+        let new_responses = new_op.get("responses");
+        for status in keys(old_responses).union(&keys(new_responses)) {
+            let old_response = old_responses.and_then(|r| r.get(status));
+            let new_response = new_responses.and_then(|r| r.get(status));
+            match (old_response, new_response) {
+                (Some(_), None) => self.record(true, location, format!("response {} removed", status)),
+                (None, Some(_)) => self.record(false, location, format!("response {} added", status)),
+                (Some(old_response), Some(new_response)) => {
+                    let what = format!("response {}", status);
+                    self.content(location, &what, old_response.get("content"), new_response.get("content"), Direction::Response);
+                }
+                (None, None) => {}
+            }
+        }
+    }
+
+    fn content(&mut self, location: &str, what: &str, old: Option<&Value>, new: Option<&Value>, direction: Direction) {
+        for media_type in keys(old).union(&keys(new)) {
+            let old_media = old.and_then(|c| c.get(media_type));
+            let new_media = new.and_then(|c| c.get(media_type));
+            match (old_media, new_media) {
+                (Some(_), None) => {
+                    // A server that stops accepting or producing a media type breaks its users
+                    self.record(true, location, format!("{}: media type '{}' removed", what, media_type));
+                }
+                (None, Some(_)) => self.record(false, location, format!("{}: media type '{}' added", what, media_type)),
+                (Some(old_media), Some(new_media)) => {
+                    if let (Some(old_schema), Some(new_schema)) = (old_media.get("schema"), new_media.get("schema")) {
+                        self.schema(location, what, old_schema, new_schema, direction, 0);
+                    }
+                }
+                (None, None) => {}
+            }
+        }
+    }
+
+    fn schema(&mut self, location: &str, what: &str, old: &Value, new: &Value, direction: Direction, depth: usize) {
+        if depth > MAX_DEPTH {
+            return;
+        }
+        let old = resolve(old, self.old_root);
+        let new = resolve(new, self.new_root);
+        if old == new {
+            return;
+        }
+
+        let old_type = schema_type(old);
+        let new_type = schema_type(new);
+        if old_type != new_type {
+            if let (Some(old_type), Some(new_type)) = (&old_type, &new_type) {
+                // Accepting a number where an integer was expected is a widening
+                let widened = old_type == "integer" && new_type == "number";
+                let breaking = !(widened && direction == Direction::Request);
+                let kind = if widened { "widened" } else { "changed" };
+                self.record(breaking, location, format!("{}: type {} from {} to {}", what, kind, old_type, new_type));
+                return;
+            }
+        }
+
+        let old_enum = old.get("enum").and_then(|e| e.as_array());
+        let new_enum = new.get("enum").and_then(|e| e.as_array());
+        if let (Some(old_enum), Some(new_enum)) = (old_enum, new_enum) {
+            let removed = old_enum.iter().any(|v| !new_enum.contains(v));
+            let added = new_enum.iter().any(|v| !old_enum.contains(v));
+            if removed && direction == Direction::Request {
+                self.record(true, location, format!("{}: allowed values narrowed", what));
+            } else if added && direction == Direction::Response {
+                self.record(true, location, format!("{}: new possible values", what));
+            } else if removed || added {
+                self.record(false, location, format!("{}: allowed values changed", what));
+            }
+        } else if old_enum.is_none() && new_enum.is_some() && direction == Direction::Request {
+            self.record(true, location, format!("{}: values restricted to an enum", what));
+        }
+
+        let old_required = string_list(old.get("required"));
+        let new_required = string_list(new.get("required"));
+        let old_properties = old.get("properties");
+        let new_properties = new.get("properties");
+
+        for name in keys(old_properties).union(&keys(new_properties)) {
+            let field = format!("{}.{}", what, name);
+            match (old_properties.and_then(|p| p.get(name)), new_properties.and_then(|p| p.get(name))) {
+                (Some(_), None) => match direction {
+                    Direction::Response => self.record(true, location, format!("{}: property removed", field)),
+                    Direction::Request => self.record(false, location, format!("{}: property removed", field)),
+                },
+                (None, Some(_)) => {
+                    if new_required.contains(name) && direction == Direction::Request {
+                        self.record(true, location, format!("{}: new required property", field));
+                    } else {
+                        self.record(false, location, format!("{}: new optional property", field));
+                    }
+                }
+                (Some(old_property), Some(new_property)) => {
+                    match direction {
+                        Direction::Request if !old_required.contains(name) && new_required.contains(name) => {
+                            self.record(true, location, format!("{}: became required", field));
+                        }
+                        Direction::Response if old_required.contains(name) && !new_required.contains(name) => {
+                            self.record(true, location, format!("{}: no longer always present", field));
+                        }
+                        _ => {}
+                    }
+                    self.schema(location, &field, old_property, new_property, direction, depth + 1);
+                }
+                (None, None) => {}
+            }
+        }
+
+        if let (Some(old_items), Some(new_items)) = (old.get("items"), new.get("items")) {
+            self.schema(location, &format!("{}[]", what), old_items, new_items, direction, depth + 1);
+        }
+    }
+}
+
+/// Compares two OpenAPI documents and classifies each difference as
+/// breaking or non-breaking for existing consumers.
+pub fn diff_documents(old: &Value, new: &Value) -> SpecDiff {
+    let mut differ = Differ {
+        old_root: old,
+        new_root: new,
+        changes: Vec::new(),
+    };
+    differ.paths(old, new);
+    SpecDiff { changes: differ.changes }
+}
\ No newline at end of file
@@ -0,0 +1,354 @@
+use serde_json::Value;
+
+use crate::models::openapi::HTTP_METHODS;
+use crate::utils::examples::resolve;
+
+const MAX_DEPTH: usize = 8;
+const UNTAGGED: &str = "Other";
+
+/// Operations listed under one tag: method, path and operation object.
+type TagGroup<'a> = (String, Vec<(&'a str, &'a str, &'a Value)>);
+
+/// One piece of the rendered document, shared by the Markdown and HTML
+/// renderers so both show the same content.
+enum Block {
+    Heading { level: usize, text: String, anchor: Option<String> },
+    Paragraph(String),
+    /// Table of contents entries: nesting level, text and anchor
+    Links(Vec<(usize, String, String)>),
+    Table { headers: Vec<&'static str>, rows: Vec<Vec<String>> },
+    Code { language: &'static str, text: String },
+}
+
+/// GitHub-style heading anchor, also used as the HTML id.
+fn slug(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            'a'..='z' | '0'..='9' | '-' | '_' => Some(c),
+            ' ' => Some('-'),
+            _ => None,
+        })
+        .collect()
+}
+
+fn text<'a>(value: &'a Value, key: &str) -> &'a str {
+    value.get(key).and_then(|v| v.as_str()).unwrap_or("")
+}
+
+/// Short type description such as `string (date-time)`, `array<User>` or
+/// `string, one of: a, b`.
+fn type_label(schema: &Value) -> String {
+    if let Some(reference) = schema.get("$ref").and_then(|r| r.as_str()) {
+        return reference.rsplit('/').next().unwrap_or(reference).to_string();
+    }
+    let mut label = match schema.get("type") {
+        Some(Value::String(t)) if t == "array" => {
+            let items = schema.get("items").map(type_label).unwrap_or_else(|| "any".to_string());
+            format!("array<{}>", items)
+        }
+        Some(Value::String(t)) => t.clone(),
+        Some(Value::Array(types)) => types.iter().filter_map(|t| t.as_str()).collect::<Vec<_>>().join(" | "),
+        _ if schema.get("properties").is_some() => "object".to_string(),
+        _ => "any".to_string(),
+    };
+    if let Some(format) = schema.get("format").and_then(|f| f.as_str()) {
+        label.push_str(&format!(" ({})", format));
+    }
+    if let Some(values) = schema.get("enum").and_then(|e| e.as_array()) {
+        let values: Vec<String> = values.iter()
+            .map(|v| v.as_str().map(|s| s.to_string()).unwrap_or_else(|| v.to_string()))
+            .collect();
+        label.push_str(&format!(", one of: {}", values.join(", ")));
+    }
+    label
+}
+
+/// Flattens a schema into `Field | Type | Required | Description` rows,
+/// naming nested fields `parent.child` and array items `parent[]`.
+fn schema_rows(schema: &Value, root: &Value, prefix: &str, depth: usize, rows: &mut Vec<Vec<String>>) {
+    let schema = resolve(schema, root);
+    if depth > MAX_DEPTH {
+        return;
+    }
+    if let Some(items) = schema.get("items") {
+        let items_resolved = resolve(items, root);
+        if items_resolved.get("properties").is_some() {
+            schema_rows(items, root, &format!("{}[]", prefix), depth + 1, rows);
+        }
+        return;
+    }
+    let Some(properties) = schema.get("properties").and_then(|p| p.as_object()) else { return };
+    let required: Vec<&str> = schema.get("required")
+        .and_then(|r| r.as_array())
+        .map(|r| r.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default();
+
+    for (name, property) in properties {
+        let field = if prefix.is_empty() { name.clone() } else { format!("{}.{}", prefix, name) };
+        let resolved = resolve(property, root);
+        rows.push(vec![
+            format!("`{}`", field),
+            type_label(property),
+            if required.contains(&name.as_str()) { "yes".to_string() } else { "no".to_string() },
+            text(resolved, "description").to_string(),
+        ]);
+        schema_rows(property, root, &field, depth + 1, rows);
+    }
+}
+
+fn media_blocks(content: &Value, root: &Value, blocks: &mut Vec<Block>) {
+    for (content_type, media) in content.as_object().into_iter().flatten() {
+        let schema = media.get("schema").cloned().unwrap_or(Value::Null);
+        blocks.push(Block::Paragraph(format!("`{}` — {}", content_type, type_label(&schema))));
+
+        let mut rows = Vec::new();
+        schema_rows(&schema, root, "", 0, &mut rows);
+        if !rows.is_empty() {
+            blocks.push(Block::Table { headers: vec!["Field", "Type", "Required", "Description"], rows });
+        }
+
+        let mut examples = Vec::new();
+        if let Some(example) = media.get("example") {
+            examples.push((String::new(), example));
+        }
+        for (name, example) in media.get("examples").and_then(|e| e.as_object()).into_iter().flatten() {
+            let label = example.get("summary").and_then(|s| s.as_str()).unwrap_or(name);
+            if let Some(value) = example.get("value") {
+                examples.push((label.to_string(), value));
+            }
+        }
+        for (label, value) in examples {
+            if !label.is_empty() {
+                blocks.push(Block::Paragraph(format!("Example: {}", label)));
+            }
+            let (language, text) = match value {
+                Value::String(raw) => ("text", raw.clone()),
+                other => ("json", serde_json::to_string_pretty(other).unwrap_or_default()),
+            };
+            blocks.push(Block::Code { language, text });
+        }
+    }
+}
+
+fn operation_blocks(method: &str, path: &str, operation: &Value, root: &Value, blocks: &mut Vec<Block>) {
+    let title = format!("{} {}", method.to_uppercase(), path);
+    blocks.push(Block::Heading { level: 3, anchor: Some(slug(&title)), text: title });
+    let summary = text(operation, "summary");
+    if !summary.is_empty() {
+        blocks.push(Block::Paragraph(format!("**{}**", summary)));
+    }
+    let description = text(operation, "description");
+    if !description.is_empty() {
+        blocks.push(Block::Paragraph(description.to_string()));
+    }
+
+    let parameters: Vec<Vec<String>> = operation.get("parameters").and_then(|p| p.as_array()).into_iter().flatten()
+        .map(|parameter| resolve(parameter, root))
+        .map(|parameter| vec![
+            format!("`{}`", text(parameter, "name")),
+            text(parameter, "in").to_string(),
+            parameter.get("schema").map(type_label).unwrap_or_default(),
+            if parameter.get("required").and_then(|r| r.as_bool()).unwrap_or(false) { "yes" } else { "no" }.to_string(),
+            text(parameter, "description").to_string(),
+        ])
+        .collect();
+    if !parameters.is_empty() {
+        blocks.push(Block::Heading { level: 4, text: "Parameters".to_string(), anchor: None });
+        blocks.push(Block::Table { headers: vec!["Name", "In", "Type", "Required", "Description"], rows: parameters });
+    }
+
+    if let Some(body) = operation.get("requestBody").map(|b| resolve(b, root)) {
+        blocks.push(Block::Heading { level: 4, text: "Request body".to_string(), anchor: None });
+        if let Some(content) = body.get("content") {
+            media_blocks(content, root, blocks);
+        }
+    }
+
+    for (status, response) in operation.get("responses").and_then(|r| r.as_object()).into_iter().flatten() {
+        let response = resolve(response, root);
+        let description = text(response, "description");
+        let heading = if description.is_empty() {
+            format!("Response {}", status)
+        } else {
+            format!("Response {} — {}", status, description)
+        };
+        blocks.push(Block::Heading { level: 4, text: heading, anchor: None });
+        if let Some(content) = response.get("content") {
+            media_blocks(content, root, blocks);
+        }
+    }
+}
+
+/// Lays out the whole document: overview, table of contents and one
+/// section per tag, in the order of the top-level `tags` list.
+fn document_blocks(document: &Value) -> Vec<Block> {
+    let info = document.get("info").cloned().unwrap_or(Value::Null);
+    let mut blocks = vec![Block::Heading { level: 1, text: text(&info, "title").to_string(), anchor: None }];
+    let version = text(&info, "version");
+    if !version.is_empty() {
+        blocks.push(Block::Paragraph(format!("Version {}", version)));
+    }
+    let description = text(&info, "description");
+    if !description.is_empty() {
+        blocks.push(Block::Paragraph(description.to_string()));
+    }
+    let servers: Vec<Vec<String>> = document.get("servers").and_then(|s| s.as_array()).into_iter().flatten()
+        .map(|server| vec![format!("`{}`", text(server, "url")), text(server, "description").to_string()])
+        .collect();
+    if !servers.is_empty() {
+        blocks.push(Block::Table { headers: vec!["Server", "Description"], rows: servers });
+    }
+
+    let mut groups: Vec<TagGroup> = document.get("tags").and_then(|t| t.as_array()).into_iter().flatten()
+        .map(|tag| (text(tag, "name").to_string(), Vec::new()))
+        .collect();
+    for (path, item) in document.get("paths").and_then(|p| p.as_object()).into_iter().flatten() {
+        for method in HTTP_METHODS {
+            let Some(operation) = item.get(*method) else { continue };
+            let tags: Vec<&str> = operation.get("tags").and_then(|t| t.as_array()).into_iter().flatten()
+                .filter_map(|t| t.as_str())
+                .collect();
+            // An operation is listed under its first tag only
+            let tag = tags.first().copied().unwrap_or(UNTAGGED);
+            match groups.iter_mut().find(|(name, _)| name == tag) {
+                Some((_, operations)) => operations.push((method, path, operation)),
+                None => groups.push((tag.to_string(), vec![(method, path, operation)])),
+            }
+        }
+    }
+    groups.retain(|(_, operations)| !operations.is_empty());
+
+    blocks.push(Block::Heading { level: 2, text: "Contents".to_string(), anchor: None });
+    let mut contents = Vec::new();
+    for (tag, operations) in &groups {
+        contents.push((0, tag.clone(), slug(tag)));
+        for (method, path, _) in operations {
+            let title = format!("{} {}", method.to_uppercase(), path);
+            let anchor = slug(&title);
+            contents.push((1, title, anchor));
+        }
+    }
+    blocks.push(Block::Links(contents));
+
+    for (tag, operations) in &groups {
+        blocks.push(Block::Heading { level: 2, text: tag.clone(), anchor: Some(slug(tag)) });
+        for (method, path, operation) in operations {
+            operation_blocks(method, path, operation, document, &mut blocks);
+        }
+    }
+    blocks
+}
+
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', "<br>")
+}
+
+/// Renders an OpenAPI document as a single Markdown page grouped by tag.
+pub fn render_markdown(document: &Value) -> String {
+    let mut out = String::new();
+    for block in document_blocks(document) {
+        match block {
+            Block::Heading { level, text, .. } => out.push_str(&format!("{} {}\n\n", "#".repeat(level), text)),
+            Block::Paragraph(text) => out.push_str(&format!("{}\n\n", text)),
+            Block::Links(links) => {
+                for (level, text, anchor) in links {
+                    out.push_str(&format!("{}- [{}](#{})\n", "  ".repeat(level), text, anchor));
+                }
+                out.push('\n');
+            }
+            Block::Table { headers, rows } => {
+                out.push_str(&format!("| {} |\n", headers.join(" | ")));
+                out.push_str(&format!("|{}\n", "---|".repeat(headers.len())));
+                for row in rows {
+                    let cells: Vec<String> = row.iter().map(|cell| markdown_cell(cell)).collect();
+                    out.push_str(&format!("| {} |\n", cells.join(" | ")));
+                }
+                out.push('\n');
+            }
+            Block::Code { language, text } => out.push_str(&format!("```{}\n{}\n```\n\n", language, text)),
+        }
+    }
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Minimal inline formatting for HTML: `code` and **bold** spans.
+fn inline_html(text: &str) -> String {
+    let mut out = String::new();
+    for (i, part) in escape_html(text).split('`').enumerate() {
+        if i % 2 == 1 {
+            out.push_str(&format!("<code>{}</code>", part));
+        } else {
+            for (j, piece) in part.split("**").enumerate() {
+                if j % 2 == 1 {
+                    out.push_str(&format!("<strong>{}</strong>", piece));
+                } else {
+                    out.push_str(piece);
+                }
+            }
+        }
+    }
+    out.replace('\n', "<br>")
+}
+
+const HTML_STYLE: &str = "body { font-family: -apple-system, Segoe UI, Helvetica, Arial, sans-serif; max-width: 960px; margin: 2em auto; padding: 0 1em; color: #222; }
+h2 { border-bottom: 1px solid #ddd; padding-bottom: .3em; margin-top: 2em; }
+h3 { font-family: monospace; background: #f3f6f9; padding: .4em .6em; border-radius: 4px; }
+table { border-collapse: collapse; margin: .5em 0 1em; }
+th, td { border: 1px solid #ddd; padding: .3em .6em; text-align: left; vertical-align: top; }
+pre { background: #f6f8fa; padding: .8em; overflow-x: auto; border-radius: 4px; }
+code { font-family: monospace; }
+ul.contents { list-style: none; padding-left: 0; }
+ul.contents li.operation { padding-left: 1.5em; font-family: monospace; }";
+
+/// Renders an OpenAPI document as a single self-contained HTML page.
+pub fn render_html(document: &Value) -> String {
+    let title = document.get("info").map(|info| text(info, "title")).unwrap_or("");
+    let mut body = String::new();
+    for block in document_blocks(document) {
+        match block {
+            Block::Heading { level, text, anchor } => {
+                let id = anchor.map(|a| format!(" id=\"{}\"", escape_html(&a))).unwrap_or_default();
+                body.push_str(&format!("<h{level}{id}>{}</h{level}>\n", escape_html(&text), level = level, id = id));
+            }
+            Block::Paragraph(text) => body.push_str(&format!("<p>{}</p>\n", inline_html(&text))),
+            Block::Links(links) => {
+                body.push_str("<ul class=\"contents\">\n");
+                for (level, text, anchor) in links {
+                    let class = if level == 0 { "tag" } else { "operation" };
+                    body.push_str(&format!(
+                        "<li class=\"{}\"><a href=\"#{}\">{}</a></li>\n",
+                        class, escape_html(&anchor), escape_html(&text)
+                    ));
+                }
+                body.push_str("</ul>\n");
+            }
+            Block::Table { headers, rows } => {
+                body.push_str("<table>\n<tr>");
+                for header in headers {
+                    body.push_str(&format!("<th>{}</th>", header));
+                }
+                body.push_str("</tr>\n");
+                for row in rows {
+                    body.push_str("<tr>");
+                    for cell in row {
+                        body.push_str(&format!("<td>{}</td>", inline_html(&cell)));
+                    }
+                    body.push_str("</tr>\n");
+                }
+                body.push_str("</table>\n");
+            }
+            Block::Code { language, text } => body.push_str(&format!(
+                "<pre><code class=\"language-{}\">{}</code></pre>\n", language, escape_html(&text)
+            )),
+        }
+    }
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{}\n</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_html(title), HTML_STYLE, body
+    )
+}
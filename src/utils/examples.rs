@@ -0,0 +1,460 @@
+use serde_json::{Map, Number, Value};
+
+use crate::utils::validate::{escape_pointer, Issue, Severity};
+
+/// What to do with an example that doesn't match its schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExampleAction {
+    /// Only report the violations
+    Report,
+    /// Remove offending examples from the document
+    Drop,
+    /// Coerce offending examples until they match the schema
+    Fix,
+}
+
+// Guards against self-referencing `$ref` cycles.
+const MAX_DEPTH: usize = 32;
+
+pub(crate) fn resolve<'a>(schema: &'a Value, root: &'a Value) -> &'a Value {
+    let mut current = schema;
+    for _ in 0..MAX_DEPTH {
+        match current.get("$ref").and_then(|r| r.as_str()).and_then(|r| r.strip_prefix('#')) {
+            Some(pointer) => match root.pointer(pointer) {
+                Some(target) => current = target,
+                None => return current,
+            },
+            None => return current,
+        }
+    }
+    current
+}
+
+pub(crate) fn schema_types(schema: &Value) -> Vec<&str> {
+    match schema.get("type") {
+        Some(Value::String(t)) => vec![t.as_str()],
+        Some(Value::Array(list)) => list.iter().filter_map(|t| t.as_str()).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn matches_type(value: &Value, schema_type: &str) -> bool {
+    match (schema_type, value) {
+        ("integer", Value::Number(n)) => n.is_i64() || n.is_u64() || n.as_f64().map(|f| f.fract() == 0.0).unwrap_or(false),
+        ("number", Value::Number(_)) => true,
+        ("string", Value::String(_)) => true,
+        ("boolean", Value::Bool(_)) => true,
+        ("array", Value::Array(_)) => true,
+        ("object", Value::Object(_)) => true,
+        ("null", Value::Null) => true,
+        _ => false,
+    }
+}
+
+pub(crate) fn allows_null(schema: &Value) -> bool {
+    schema.get("nullable") == Some(&Value::Bool(true)) || schema_types(schema).contains(&"null")
+}
+
+/// Validates `instance` against an OpenAPI schema object, collecting
+/// `(pointer, message)` pairs. `pointer` is where `instance` lives, so the
+/// reported pointers can be used directly on the containing document.
+/// Properties that aren't `required` may be `null`: inferred schemas can't
+/// know the type of a value that was null in the saved response.
+pub fn validate_instance(instance: &Value, schema: &Value, root: &Value, pointer: &str, violations: &mut Vec<(String, String)>) {
+    validate_at_depth(instance, schema, root, pointer, violations, 0);
+}
+
+fn validate_at_depth(instance: &Value, schema: &Value, root: &Value, pointer: &str, violations: &mut Vec<(String, String)>, depth: usize) {
+    if depth > MAX_DEPTH {
+        return;
+    }
+    let schema = resolve(schema, root);
+    if !schema.is_object() {
+        return;
+    }
+
+    if let Some(options) = schema.get("enum").and_then(|e| e.as_array()) {
+        if !options.contains(instance) {
+            violations.push((pointer.to_string(), format!("{} is not one of the allowed values", instance)));
+            return;
+        }
+    }
+
+    if instance.is_null() && allows_null(schema) {
+        return;
+    }
+
+    let types = schema_types(schema);
+    if !types.is_empty() && !types.iter().any(|t| matches_type(instance, t)) {
+        violations.push((pointer.to_string(), format!("expected {}, found {}", types.join(" or "), type_name(instance))));
+        return;
+    }
+
+    match instance {
+        Value::Object(object) => {
+            let required: Vec<&str> = schema.get("required")
+                .and_then(|r| r.as_array())
+                .map(|r| r.iter().filter_map(|n| n.as_str()).collect())
+                .unwrap_or_default();
+            for name in &required {
+                if !object.contains_key(*name) {
+                    violations.push((pointer.to_string(), format!("missing required property '{}'", name)));
+                }
+            }
+            if let Some(properties) = schema.get("properties").and_then(|p| p.as_object()) {
+                for (name, value) in object {
+                    if let Some(property) = properties.get(name) {
+                        if value.is_null() && !required.contains(&name.as_str()) {
+                            continue;
+                        }
+                        let child = format!("{}/{}", pointer, escape_pointer(name));
+                        validate_at_depth(value, property, root, &child, violations, depth + 1);
+                    }
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    let child = format!("{}/{}", pointer, i);
+                    validate_at_depth(item, item_schema, root, &child, violations, depth + 1);
+                }
+            }
+            if let Some(min) = schema.get("minItems").and_then(|m| m.as_u64()) {
+                if (items.len() as u64) < min {
+                    violations.push((pointer.to_string(), format!("expected at least {} item(s)", min)));
+                }
+            }
+        }
+        Value::String(s) => {
+            let length = s.chars().count() as u64;
+            if let Some(min) = schema.get("minLength").and_then(|m| m.as_u64()) {
+                if length < min {
+                    violations.push((pointer.to_string(), format!("string is shorter than {}", min)));
+                }
+            }
+            if let Some(max) = schema.get("maxLength").and_then(|m| m.as_u64()) {
+                if length > max {
+                    violations.push((pointer.to_string(), format!("string is longer than {}", max)));
+                }
+            }
+        }
+        Value::Number(n) => {
+            let n = n.as_f64().unwrap_or(0.0);
+            if let Some(min) = schema.get("minimum").and_then(|m| m.as_f64()) {
+                if n < min {
+                    violations.push((pointer.to_string(), format!("{} is less than the minimum {}", n, min)));
+                }
+            }
+            if let Some(max) = schema.get("maximum").and_then(|m| m.as_f64()) {
+                if n > max {
+                    violations.push((pointer.to_string(), format!("{} is greater than the maximum {}", n, max)));
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// A placeholder value that satisfies `schema`: every property, one array
+/// item, and `example`/`default`/first `enum` value where the schema has one.
+pub fn sample_for_schema(schema: &Value, root: &Value) -> Value {
+    sample_at_depth(schema, root, 0)
+}
+
+fn sample_at_depth(schema: &Value, root: &Value, depth: usize) -> Value {
+    let schema = resolve(schema, root);
+    if depth > MAX_DEPTH {
+        return Value::Null;
+    }
+
+    for key in ["example", "default"] {
+        if let Some(value) = schema.get(key) {
+            return value.clone();
+        }
+    }
+    if let Some(first) = schema.get("enum").and_then(|e| e.as_array()).and_then(|e| e.first()) {
+        return first.clone();
+    }
+
+    let types = schema_types(schema);
+    let schema_type = types.iter()
+        .find(|t| **t != "null")
+        .copied()
+        .or_else(|| if schema.get("properties").is_some() { Some("object") } else { None })
+        .unwrap_or("string");
+
+    match schema_type {
+        "object" => {
+            let mut object = Map::new();
+            if let Some(properties) = schema.get("properties").and_then(|p| p.as_object()) {
+                for (name, property) in properties {
+                    object.insert(name.clone(), sample_at_depth(property, root, depth + 1));
+                }
+            }
+            Value::Object(object)
+        }
+        "array" => match schema.get("items") {
+            Some(items) => Value::Array(vec![sample_at_depth(items, root, depth + 1)]),
+            None => Value::Array(Vec::new()),
+        },
+        "integer" => Value::Number(Number::from(0)),
+        "number" => Value::Number(Number::from(0)),
+        "boolean" => Value::Bool(false),
+        _ => match schema.get("format").and_then(|f| f.as_str()) {
+            Some("date-time") => Value::String("1970-01-01T00:00:00Z".to_string()),
+            Some("date") => Value::String("1970-01-01".to_string()),
+            Some("email") => Value::String("user@example.com".to_string()),
+            Some("uuid") => Value::String("00000000-0000-0000-0000-000000000000".to_string()),
+            Some("uri") => Value::String("https://example.com".to_string()),
+            _ => Value::String("string".to_string()),
+        },
+    }
+}
+
+/// Coerces `instance` towards `schema`: converts scalars written with the
+/// wrong type (`"42"` for a number), fills in missing required properties,
+/// pads or truncates strings and arrays to their length limits, clamps
+/// numbers into range and replaces anything else that doesn't fit with a
+/// sample value.
+pub fn fix_instance(instance: &mut Value, schema: &Value, root: &Value) {
+    fix_at_depth(instance, schema, root, 0);
+}
+
+fn fix_at_depth(instance: &mut Value, schema: &Value, root: &Value, depth: usize) {
+    let schema = resolve(schema, root);
+    if depth > MAX_DEPTH || !schema.is_object() {
+        return;
+    }
+
+    if let Some(options) = schema.get("enum").and_then(|e| e.as_array()) {
+        if !options.contains(instance) {
+            *instance = options.first().cloned().unwrap_or(Value::Null);
+        }
+        return;
+    }
+
+    if instance.is_null() && allows_null(schema) {
+        return;
+    }
+
+    let types = schema_types(schema);
+    if !types.is_empty() && !types.iter().any(|t| matches_type(instance, t)) {
+        // A coerced value may still be out of range, so it goes on below
+        match types.iter().find_map(|t| coerce(instance, t)) {
+            Some(coerced) => *instance = coerced,
+            None => {
+                *instance = sample_at_depth(schema, root, depth);
+                return;
+            }
+        }
+    }
+
+    match instance {
+        Value::Object(object) => {
+            let properties = schema.get("properties").and_then(|p| p.as_object());
+            let required: Vec<String> = schema.get("required")
+                .and_then(|r| r.as_array())
+                .map(|r| r.iter().filter_map(|n| n.as_str().map(|n| n.to_string())).collect())
+                .unwrap_or_default();
+            for name in &required {
+                if !object.contains_key(name) {
+                    let sample = properties
+                        .and_then(|p| p.get(name))
+                        .map(|property| sample_at_depth(property, root, depth + 1))
+                        .unwrap_or(Value::Null);
+                    object.insert(name.clone(), sample);
+                }
+            }
+            if let Some(properties) = properties {
+                for (name, value) in object.iter_mut() {
+                    if value.is_null() && !required.contains(name) {
+                        continue;
+                    }
+                    if let Some(property) = properties.get(name) {
+                        fix_at_depth(value, property, root, depth + 1);
+                    }
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for item in items.iter_mut() {
+                    fix_at_depth(item, item_schema, root, depth + 1);
+                }
+            }
+            if let Some(min) = schema.get("minItems").and_then(|m| m.as_u64()) {
+                let item_schema = schema.get("items").cloned().unwrap_or(Value::Null);
+                while (items.len() as u64) < min {
+                    items.push(sample_at_depth(&item_schema, root, depth + 1));
+                }
+            }
+        }
+        Value::String(s) => {
+            if let Some(max) = schema.get("maxLength").and_then(|m| m.as_u64()) {
+                *s = s.chars().take(max as usize).collect();
+            }
+            if let Some(min) = schema.get("minLength").and_then(|m| m.as_u64()) {
+                let missing = (min as usize).saturating_sub(s.chars().count());
+                s.push_str(&"x".repeat(missing));
+            }
+        }
+        Value::Number(n) => {
+            let mut value = n.as_f64().unwrap_or(0.0);
+            if let Some(min) = schema.get("minimum").and_then(|m| m.as_f64()) {
+                value = value.max(min);
+            }
+            if let Some(max) = schema.get("maximum").and_then(|m| m.as_f64()) {
+                value = value.min(max);
+            }
+            if value != n.as_f64().unwrap_or(0.0) {
+                let clamped = if n.is_f64() { Number::from_f64(value) } else { Some(Number::from(value as i64)) };
+                if let Some(clamped) = clamped {
+                    *n = clamped;
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn coerce(value: &Value, schema_type: &str) -> Option<Value> {
+    match (schema_type, value) {
+        ("integer", Value::String(s)) => s.trim().parse::<i64>().ok().map(|n| Value::Number(Number::from(n))),
+        ("integer", Value::Number(n)) => n.as_f64().map(|f| Value::Number(Number::from(f.round() as i64))),
+        ("number", Value::String(s)) => s.trim().parse::<f64>().ok().and_then(Number::from_f64).map(Value::Number),
+        ("boolean", Value::String(s)) => match s.trim() {
+            "true" => Some(Value::Bool(true)),
+            "false" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        ("string", Value::Number(n)) => Some(Value::String(n.to_string())),
+        ("string", Value::Bool(b)) => Some(Value::String(b.to_string())),
+        ("array", value) if !value.is_null() => Some(Value::Array(vec![value.clone()])),
+        _ => None,
+    }
+}
+
+/// Checks every media type example (`example` and `examples.*.value`) in
+/// the document against the schema next to it, applying `action` to the
+/// ones that don't match. Returns one warning per violation.
+pub fn check_examples(document: &mut Value, action: ExampleAction) -> Vec<Issue> {
+    let root = document.clone();
+    let mut issues = Vec::new();
+
+    let Some(paths) = document.get_mut("paths").and_then(|p| p.as_object_mut()) else {
+        return issues;
+    };
+
+    for (path, item) in paths.iter_mut() {
+        let Some(item) = item.as_object_mut() else { continue };
+        for (method, operation) in item.iter_mut() {
+            let Some(operation) = operation.as_object_mut() else { continue };
+            let pointer = format!("/paths/{}/{}", escape_pointer(path), escape_pointer(method));
+
+            if let Some(content) = operation.get_mut("requestBody").and_then(|b| b.get_mut("content")) {
+                check_content(content, &format!("{}/requestBody/content", pointer), &root, action, &mut issues);
+            }
+
+            if let Some(responses) = operation.get_mut("responses").and_then(|r| r.as_object_mut()) {
+                for (status, response) in responses.iter_mut() {
+                    if let Some(content) = response.get_mut("content") {
+                        let content_pointer = format!("{}/responses/{}/content", pointer, escape_pointer(status));
+                        check_content(content, &content_pointer, &root, action, &mut issues);
+                    }
+                }
+            }
+        }
+    }
+
+    issues
+}
+
+fn check_content(content: &mut Value, pointer: &str, root: &Value, action: ExampleAction, issues: &mut Vec<Issue>) {
+    let Some(content) = content.as_object_mut() else { return };
+
+    for (media_type, media) in content.iter_mut() {
+        let Some(media) = media.as_object_mut() else { continue };
+        let Some(schema) = media.get("schema").cloned() else { continue };
+        let media_pointer = format!("{}/{}", pointer, escape_pointer(media_type));
+
+        if let Some(example) = media.get_mut("example") {
+            if !check_example(example, &schema, root, &format!("{}/example", media_pointer), action, issues) {
+                media.remove("example");
+            }
+        }
+
+        if let Some(examples) = media.get_mut("examples").and_then(|e| e.as_object_mut()) {
+            let mut dropped = Vec::new();
+            for (name, example) in examples.iter_mut() {
+                if example.get("$ref").is_some() {
+                    continue;
+                }
+                let Some(value) = example.get_mut("value") else { continue };
+                let example_pointer = format!("{}/examples/{}/value", media_pointer, escape_pointer(name));
+                if !check_example(value, &schema, root, &example_pointer, action, issues) {
+                    dropped.push(name.clone());
+                }
+            }
+            for name in dropped {
+                examples.remove(&name);
+            }
+        }
+    }
+}
+
+/// Returns false when the example should be removed.
+fn check_example(example: &mut Value, schema: &Value, root: &Value, pointer: &str, action: ExampleAction, issues: &mut Vec<Issue>) -> bool {
+    let mut violations = Vec::new();
+    validate_instance(example, schema, root, pointer, &mut violations);
+    if violations.is_empty() {
+        return true;
+    }
+
+    // A fix is only kept if the result conforms; anything left is dropped
+    let fixed = match action {
+        ExampleAction::Fix => {
+            let mut fixed = example.clone();
+            fix_instance(&mut fixed, schema, root);
+            let mut remaining = Vec::new();
+            validate_instance(&fixed, schema, root, pointer, &mut remaining);
+            remaining.is_empty().then_some(fixed)
+        }
+        _ => None,
+    };
+
+    let outcome = match (action, &fixed) {
+        (ExampleAction::Report, _) => "",
+        (ExampleAction::Drop, _) => " (example dropped)",
+        (ExampleAction::Fix, Some(_)) => " (example fixed)",
+        (ExampleAction::Fix, None) => " (example could not be fixed and was dropped)",
+    };
+    for (violation_pointer, message) in violations {
+        issues.push(Issue {
+            severity: Severity::Warning,
+            pointer: violation_pointer,
+            message: format!("example does not match schema: {}{}", message, outcome),
+        });
+    }
+
+    match (action, fixed) {
+        (ExampleAction::Report, _) => true,
+        (ExampleAction::Fix, Some(fixed)) => {
+            *example = fixed;
+            true
+        }
+        _ => false,
+    }
+}
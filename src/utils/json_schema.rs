@@ -0,0 +1,233 @@
+use std::collections::{HashMap, HashSet};
+use indexmap::IndexMap;
+use serde_json::{json, Map, Value};
+
+use crate::models::openapi::{Content, OpenAPISpec, Schema};
+use crate::utils::codegen::type_name;
+
+pub const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+/// File holding the object schemas used by more than one payload.
+pub const DEFINITIONS_FILE: &str = "definitions.schema.json";
+
+/// One JSON Schema document to write.
+pub struct SchemaFile {
+    pub file_name: String,
+    pub document: Value,
+}
+
+/// `Get user` and `GET /users/{id}` become `get-user` and `get-users-id`.
+fn file_stem(text: &str) -> String {
+    let mut stem = String::new();
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            stem.push(c.to_ascii_lowercase());
+        } else if !stem.is_empty() && !stem.ends_with('-') {
+            stem.push('-');
+        }
+    }
+    stem.trim_end_matches('-').to_string()
+}
+
+/// Picks the JSON body of a request or response, if it has one.
+fn body(content: &HashMap<String, Content>) -> Option<&Schema> {
+    let mut types: Vec<&String> = content.keys().collect();
+    types.sort();
+    types.iter()
+        .find(|content_type| content_type.contains("json"))
+        .or_else(|| types.first())
+        .map(|content_type| &content[*content_type].schema)
+}
+
+fn is_shareable(schema: &Schema) -> bool {
+    schema.schema_type == "object" && schema.properties.as_ref().is_some_and(|p| !p.is_empty())
+}
+
+fn fingerprint(schema: &Schema) -> String {
+    serde_json::to_string(schema).unwrap_or_default()
+}
+
+fn count_objects(schema: &Schema, counts: &mut HashMap<String, usize>) {
+    if is_shareable(schema) {
+        *counts.entry(fingerprint(schema)).or_default() += 1;
+    }
+    for property in schema.properties.iter().flat_map(|p| p.values()) {
+        count_objects(property, counts);
+    }
+    if let Some(items) = &schema.items {
+        count_objects(items, counts);
+    }
+}
+
+/// Turns `Schema`s into JSON Schema, moving objects that occur more than
+/// once into `$defs` of the definitions file.
+struct Exporter {
+    counts: HashMap<String, usize>,
+    /// Definition name of each shared object, by fingerprint
+    names: HashMap<String, String>,
+    used: HashSet<String>,
+    defs: IndexMap<String, Value>,
+}
+
+impl Exporter {
+    fn shared(&mut self, schema: &Schema, suggested: &str) -> Value {
+        let key = fingerprint(schema);
+        let name = match self.names.get(&key) {
+            Some(name) => name.clone(),
+            None => {
+                let base = type_name(suggested);
+                let mut name = base.clone();
+                let mut n = 2;
+                while !self.used.insert(name.clone()) {
+                    name = format!("{}{}", base, n);
+                    n += 1;
+                }
+                self.names.insert(key, name.clone());
+                let definition = self.convert(schema, &name, true);
+                self.defs.insert(name.clone(), definition);
+                name
+            }
+        };
+        json!({ "$ref": format!("{}#/$defs/{}", DEFINITIONS_FILE, name) })
+    }
+
+    /// `top` is true for a payload or definition itself, which is never
+    /// replaced by a reference.
+    fn convert(&mut self, schema: &Schema, suggested: &str, top: bool) -> Value {
+        if !top && is_shareable(schema) && self.counts.get(&fingerprint(schema)).copied().unwrap_or(0) > 1 {
+            return self.shared(schema, suggested);
+        }
+        let mut out = Map::new();
+        // JSON Schema has no `nullable`; null is one of the types instead
+        if schema.keywords.get("nullable") == Some(&Value::Bool(true)) {
+            out.insert("type".to_string(), json!([schema.schema_type, "null"]));
+        } else {
+            out.insert("type".to_string(), Value::String(schema.schema_type.clone()));
+        }
+        if let Some(properties) = &schema.properties {
+            let properties: Map<String, Value> = properties.iter()
+                .map(|(name, property)| (name.clone(), self.convert(property, name, false)))
+                .collect();
+            out.insert("properties".to_string(), Value::Object(properties));
+        }
+        if let Some(required) = &schema.required {
+            out.insert("required".to_string(), json!(required));
+        }
+        if let Some(items) = &schema.items {
+            let items = self.convert(items, &format!("{}Item", suggested), false);
+            out.insert("items".to_string(), items);
+        }
+        for (keyword, value) in &schema.keywords {
+            if keyword != "nullable" {
+                out.insert(keyword.clone(), value.clone());
+            }
+        }
+        Value::Object(out)
+    }
+}
+
+/// Writes every request and response body of `spec` as a standalone JSON
+/// Schema (draft 2020-12) named by operation and status, such as
+/// `create-user.request.schema.json` and `create-user.201.schema.json`.
+/// Objects used by several payloads go into `definitions.schema.json` and
+/// are referenced from there. Each `$id` is `base` followed by the file
+/// name, so with an empty `base` references resolve next to the files.
+pub fn export_json_schemas(spec: &OpenAPISpec, base: &str) -> Vec<SchemaFile> {
+    // (file stem, title, schema) of every payload
+    let mut payloads = Vec::new();
+    let mut stems = HashSet::new();
+    for (path, operations) in &spec.paths {
+        for (method, operation) in operations {
+            let label = if operation.summary.trim().is_empty() {
+                format!("{} {}", method, path)
+            } else {
+                operation.summary.clone()
+            };
+            let base_stem = file_stem(&label);
+            let mut stem = base_stem.clone();
+            let mut n = 2;
+            while !stems.insert(stem.clone()) {
+                stem = format!("{}-{}", base_stem, n);
+                n += 1;
+            }
+
+            let endpoint = format!("{} {}", method.to_uppercase(), path);
+            if let Some(schema) = operation.request_body.as_ref().and_then(|b| body(&b.content)) {
+                payloads.push((format!("{}.request", stem), format!("{} request body", endpoint), schema, label.clone()));
+            }
+            for (status, response) in &operation.responses {
+                if let Some(schema) = body(&response.content) {
+                    payloads.push((format!("{}.{}", stem, status), format!("{} {} response", endpoint, status), schema, label.clone()));
+                }
+            }
+        }
+    }
+
+    let mut counts = HashMap::new();
+    for (_, _, schema, _) in &payloads {
+        count_objects(schema, &mut counts);
+    }
+    let mut exporter = Exporter { counts, names: HashMap::new(), used: HashSet::new(), defs: IndexMap::new() };
+
+    let base = if base.is_empty() || base.ends_with('/') { base.to_string() } else { format!("{}/", base) };
+    let mut files = Vec::new();
+    for (stem, title, schema, label) in payloads {
+        let file_name = format!("{}.schema.json", stem);
+        let mut document = Map::new();
+        document.insert("$schema".to_string(), json!(DRAFT));
+        document.insert("$id".to_string(), json!(format!("{}{}", base, file_name)));
+        document.insert("title".to_string(), json!(title));
+        if let Value::Object(body) = exporter.convert(schema, &label, true) {
+            document.extend(body);
+        }
+        files.push(SchemaFile { file_name, document: Value::Object(document) });
+    }
+
+    if !exporter.defs.is_empty() {
+        let document = json!({
+            "$schema": DRAFT,
+            "$id": format!("{}{}", base, DEFINITIONS_FILE),
+            "title": format!("{} shared definitions", spec.info.title),
+            "$defs": exporter.defs,
+        });
+        files.push(SchemaFile { file_name: DEFINITIONS_FILE.to_string(), document });
+    }
+    files
+}
+
+/// Rewrites `nullable: true` as a `"null"` entry in `type` throughout an
+/// OpenAPI 3.1 document, whose schemas are JSON Schema and have no
+/// `nullable`. Examples and other literal values are left as they are.
+pub fn use_type_arrays(document: &mut Value) {
+    match document {
+        Value::Object(object) => {
+            if object.get("nullable") == Some(&Value::Bool(true)) {
+                if let Some(Value::String(schema_type)) = object.get("type") {
+                    let types = json!([schema_type, "null"]);
+                    object.insert("type".to_string(), types);
+                    object.shift_remove("nullable");
+                    if let Some(Value::Array(options)) = object.get_mut("enum") {
+                        if !options.contains(&Value::Null) {
+                            options.push(Value::Null);
+                        }
+                    }
+                }
+            }
+            for (key, child) in object.iter_mut() {
+                match (key.as_str(), child) {
+                    ("example" | "examples" | "default" | "enum" | "const", _) => {}
+                    // Maps of schemas, whose keys may be any of the names above
+                    ("properties" | "patternProperties" | "$defs" | "schemas", Value::Object(schemas)) => {
+                        schemas.values_mut().for_each(use_type_arrays);
+                    }
+                    (_, child) => use_type_arrays(child),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                use_type_arrays(item);
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,315 @@
+use std::collections::{HashMap, HashSet};
+use indexmap::IndexMap;
+use serde_json::Value;
+
+use crate::models::openapi::*;
+
+/// Combines the specs converted from several collections into one document.
+/// Each entry is `(collection name, spec)`; the name is used to namespace
+/// tags and to rename component schemas and examples whose names clash.
+/// The OpenAPI version is the first spec's, and `info.version` the one the
+/// specs share (`1.0.0` when they differ).
+pub fn merge_specs(specs: Vec<(String, OpenAPISpec)>) -> OpenAPISpec {
+    let titles: Vec<String> = specs.iter().map(|(collection, _)| collection.clone()).collect();
+    let openapi = specs.first()
+        .map(|(_, spec)| spec.openapi.clone())
+        .unwrap_or_else(|| "3.0.0".to_string());
+    let versions: HashSet<&str> = specs.iter().map(|(_, spec)| spec.info.version.as_str()).collect();
+    let version = match versions.into_iter().collect::<Vec<_>>().as_slice() {
+        [version] => version.to_string(),
+        _ => "1.0.0".to_string(),
+    };
+
+    let mut description = format!("Combined from: {}", titles.join(", "));
+    for (collection, spec) in &specs {
+        if !spec.info.description.is_empty() {
+            description.push_str(&format!("\n\n## {}\n\n{}", collection, spec.info.description));
+        }
+    }
+
+    let mut merged = OpenAPISpec {
+        openapi,
+        info: OpenAPIInfo {
+            title: titles.join(" + "),
+            description,
+            version,
+            terms_of_service: None,
+            contact: None,
+            license: None,
+            extensions: IndexMap::new(),
+        },
+        external_docs: None,
+        servers: Vec::new(),
+        paths: IndexMap::new(),
+        components: Components {
+            schemas: IndexMap::new(),
+        },
+        tags: Vec::new(),
+        extensions: IndexMap::new(),
+    };
+
+    let mut tags_set = HashSet::new();
+
+    for (collection, mut spec) in specs {
+        let renames = merge_schemas(&mut merged.components.schemas, spec.components.schemas, &collection);
+        if !renames.is_empty() {
+            for operation in spec.paths.values_mut().flat_map(|operations| operations.values_mut()) {
+                rewrite_operation_refs(operation, &renames);
+            }
+        }
+        if merged.external_docs.is_none() {
+            merged.external_docs = spec.external_docs;
+        }
+        for (key, value) in spec.extensions {
+            merged.extensions.entry(key).or_insert(value);
+        }
+        for server in spec.servers {
+            if !merged.servers.contains(&server) {
+                merged.servers.push(server);
+            }
+        }
+
+        for (path, operations) in spec.paths {
+            let path_map = merged.paths.entry(path).or_default();
+            for (method, mut operation) in operations {
+                operation.tags = namespace_tags(&collection, &operation.tags);
+                for tag in &operation.tags {
+                    tags_set.insert(tag.clone());
+                }
+                match path_map.get_mut(&method) {
+                    Some(existing) => merge_operation(existing, operation, &collection),
+                    None => {
+                        path_map.insert(method, operation);
+                    }
+                }
+            }
+        }
+    }
+
+    merged.tags = tags_set.into_iter()
+        .map(|tag| Tag { name: tag })
+        .collect();
+    merged.tags.sort_by(|a, b| a.name.cmp(&b.name));
+
+    merged
+}
+
+fn namespace_tags(collection: &str, tags: &[String]) -> Vec<String> {
+    if tags.is_empty() {
+        return vec![collection.to_string()];
+    }
+    tags.iter()
+        .map(|tag| format!("{} / {}", collection, tag))
+        .collect()
+}
+
+/// `payments-api` becomes `PaymentsApi`, used to tell apart schemas and
+/// examples that two collections define under the same name.
+fn collection_suffix(collection: &str) -> String {
+    collection
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// Adds `incoming` schemas to `schemas`. Identical definitions are shared;
+/// different definitions under the same name are renamed with the collection
+/// suffix, and references between the incoming schemas follow the rename.
+/// Returns the renames, for the references in the collection's operations.
+fn merge_schemas(
+    schemas: &mut IndexMap<String, Value>,
+    incoming: IndexMap<String, Value>,
+    collection: &str,
+) -> HashMap<String, String> {
+    // A schema referencing a renamed one differs once its references follow
+    // the rename, so repeat until no more names clash
+    let mut renames: HashMap<String, String> = HashMap::new();
+    loop {
+        let mut clashing = Vec::new();
+        for (name, schema) in &incoming {
+            if renames.contains_key(name) {
+                continue;
+            }
+            let mut schema = schema.clone();
+            rewrite_refs(&mut schema, &renames);
+            if schemas.get(name).is_some_and(|existing| *existing != schema) {
+                clashing.push(name.clone());
+            }
+        }
+        if clashing.is_empty() {
+            break;
+        }
+        for name in clashing {
+            let base = format!("{}{}", name, collection_suffix(collection));
+            let mut new_name = base.clone();
+            let mut counter = 2;
+            while schemas.contains_key(&new_name) || renames.values().any(|taken| *taken == new_name) {
+                new_name = format!("{}{}", base, counter);
+                counter += 1;
+            }
+            renames.insert(name, new_name);
+        }
+    }
+
+    for (name, mut schema) in incoming {
+        rewrite_refs(&mut schema, &renames);
+        let name = renames.get(&name).cloned().unwrap_or(name);
+        schemas.entry(name).or_insert(schema);
+    }
+    renames
+}
+
+fn rewrite_refs(value: &mut Value, renames: &HashMap<String, String>) {
+    match value {
+        Value::Object(obj) => {
+            for (key, child) in obj.iter_mut() {
+                if key == "$ref" {
+                    if let Some(name) = child.as_str().and_then(|r| r.strip_prefix("#/components/schemas/")) {
+                        if let Some(new_name) = renames.get(name) {
+                            *child = Value::String(format!("#/components/schemas/{}", new_name));
+                        }
+                    }
+                } else {
+                    rewrite_refs(child, renames);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                rewrite_refs(item, renames);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn rewrite_schema_refs(schema: &mut Schema, renames: &HashMap<String, String>) {
+    // `$ref` and the keywords holding subschemas live among the extra keywords
+    let mut keywords = Value::Object(std::mem::take(&mut schema.keywords).into_iter().collect());
+    rewrite_refs(&mut keywords, renames);
+    if let Value::Object(keywords) = keywords {
+        schema.keywords = keywords.into_iter().collect();
+    }
+    for property in schema.properties.iter_mut().flat_map(|p| p.values_mut()) {
+        rewrite_schema_refs(property, renames);
+    }
+    if let Some(items) = schema.items.as_mut() {
+        rewrite_schema_refs(items, renames);
+    }
+}
+
+fn rewrite_operation_refs(operation: &mut Operation, renames: &HashMap<String, String>) {
+    for parameter in &mut operation.parameters {
+        rewrite_schema_refs(&mut parameter.schema, renames);
+    }
+    let bodies = operation.request_body.iter_mut().flat_map(|body| body.content.values_mut());
+    let responses = operation.responses.values_mut().flat_map(|response| response.content.values_mut());
+    for content in bodies.chain(responses) {
+        rewrite_schema_refs(&mut content.schema, renames);
+    }
+}
+
+/// Adds `incoming` examples to `examples`. An example whose name is taken by
+/// a different one gets the collection suffix instead of replacing it.
+fn merge_examples(examples: &mut IndexMap<String, Example>, incoming: IndexMap<String, Example>, collection: &str) {
+    for (name, example) in incoming {
+        let name = match examples.get(&name) {
+            None => name,
+            Some(existing) if existing.value == example.value => continue,
+            Some(_) => {
+                let base = format!("{}{}", name, collection_suffix(collection));
+                let mut new_name = base.clone();
+                let mut counter = 2;
+                while examples.contains_key(&new_name) {
+                    new_name = format!("{}{}", base, counter);
+                    counter += 1;
+                }
+                new_name
+            }
+        };
+        examples.insert(name, example);
+    }
+}
+
+/// Folds an operation from another collection into one already present at
+/// the same path and method.
+fn merge_operation(existing: &mut Operation, incoming: Operation, collection: &str) {
+    if existing.summary.is_empty() {
+        existing.summary = incoming.summary;
+    }
+    if existing.description.is_empty() {
+        existing.description = incoming.description;
+    }
+
+    // Only keep a parameter required if every collection sends it; path
+    // parameters are always required.
+    for current in existing.parameters.iter_mut() {
+        let shared = incoming.parameters.iter().any(|p| p.name == current.name && p.param_in == current.param_in);
+        if !shared && current.param_in != "path" {
+            current.required = Some(false);
+        }
+    }
+
+    for mut parameter in incoming.parameters {
+        match existing.parameters.iter_mut().find(|p| p.name == parameter.name && p.param_in == parameter.param_in) {
+            Some(current) => {
+                if parameter.required != Some(true) {
+                    current.required = parameter.required;
+                }
+                if current.description.is_none() {
+                    current.description = parameter.description;
+                }
+            }
+            None => {
+                if parameter.param_in != "path" {
+                    parameter.required = Some(false);
+                }
+                existing.parameters.push(parameter);
+            }
+        }
+    }
+
+    if existing.request_body.is_none() {
+        existing.request_body = incoming.request_body;
+    }
+
+    for (status, response) in incoming.responses {
+        match existing.responses.get_mut(&status) {
+            Some(current) => {
+                for (content_type, content) in response.content {
+                    match current.content.get_mut(&content_type) {
+                        Some(current_content) => {
+                            if let Some(new_examples) = content.examples {
+                                let examples = current_content.examples.get_or_insert_with(IndexMap::new);
+                                merge_examples(examples, new_examples, collection);
+                            }
+                        }
+                        None => {
+                            current.content.insert(content_type, content);
+                        }
+                    }
+                }
+            }
+            None => {
+                existing.responses.insert(status, response);
+            }
+        }
+    }
+
+    for tag in incoming.tags {
+        if !existing.tags.contains(&tag) {
+            existing.tags.push(tag);
+        }
+    }
+
+    for (key, value) in incoming.extensions {
+        existing.extensions.entry(key).or_insert(value);
+    }
+}
\ No newline at end of file
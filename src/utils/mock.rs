@@ -0,0 +1,271 @@
+use std::io;
+use serde_json::Value;
+use tiny_http::{Header, Response, Server};
+
+use crate::models::openapi::HTTP_METHODS;
+use crate::utils::examples::sample_for_schema;
+use crate::utils::path::{parse_query_string, percent_decode, split_url};
+
+/// Query parameter selecting the response status, for clients that can't
+/// set a `Prefer` header.
+pub const CODE_PARAM: &str = "__code";
+/// Query parameter selecting a named example.
+pub const EXAMPLE_PARAM: &str = "__example";
+
+#[derive(Clone)]
+enum Segment {
+    Literal(String),
+    Param,
+}
+
+struct Route {
+    method: String,
+    template: String,
+    segments: Vec<Segment>,
+    operation: Value,
+}
+
+impl Route {
+    fn literals(&self) -> usize {
+        self.segments.iter().filter(|s| matches!(s, Segment::Literal(_))).count()
+    }
+
+    fn matches(&self, segments: &[&str]) -> bool {
+        self.segments.len() == segments.len()
+            && self.segments.iter().zip(segments).all(|(expected, actual)| match expected {
+                Segment::Literal(literal) => literal == actual,
+                Segment::Param => !actual.is_empty(),
+            })
+    }
+}
+
+/// A reply chosen by the mock, and where its body came from.
+#[derive(Debug)]
+pub struct MockResponse {
+    pub status: u16,
+    pub content_type: Option<String>,
+    pub body: Vec<u8>,
+    /// `example 'name'`, `schema`, `no content` or the reason for an error
+    pub source: String,
+}
+
+impl MockResponse {
+    fn error(status: u16, message: String) -> Self {
+        let body = serde_json::to_vec_pretty(&serde_json::json!({ "error": message })).unwrap_or_default();
+        MockResponse {
+            status,
+            content_type: Some("application/json".to_string()),
+            body,
+            source: message,
+        }
+    }
+}
+
+/// Answers requests from the operations, examples and schemas of an
+/// OpenAPI document.
+pub struct MockServer {
+    document: Value,
+    routes: Vec<Route>,
+    base_paths: Vec<String>,
+}
+
+fn split_path(path: &str) -> Vec<&str> {
+    path.trim_matches('/').split('/').filter(|s| !s.is_empty()).collect()
+}
+
+/// Reads `Prefer: code=404, example=notFound`.
+fn preference<'a>(prefer: Option<&'a str>, key: &str) -> Option<&'a str> {
+    prefer?
+        .split([',', ';'])
+        .filter_map(|part| part.split_once('='))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case(key))
+        .map(|(_, value)| value.trim().trim_matches('"'))
+}
+
+/// Whether a response key (`404` or `4XX`) covers a status.
+fn covers(key: &str, status: &str) -> bool {
+    key == status
+        || (key.len() == 3 && key.ends_with("XX") && status.starts_with(&key[..1]))
+}
+
+/// The status sent for a response key: `4XX` answers with 400 and
+/// `default`, which usually describes errors, with 500.
+fn status_for(key: &str) -> u16 {
+    match key.parse() {
+        Ok(status) => status,
+        Err(_) if key.len() == 3 && key.ends_with("XX") => key[..1].parse::<u16>().map(|class| class * 100).unwrap_or(500),
+        Err(_) => 500,
+    }
+}
+
+impl MockServer {
+    pub fn new(document: Value) -> Self {
+        let mut routes = Vec::new();
+        for (template, item) in document.get("paths").and_then(|p| p.as_object()).into_iter().flatten() {
+            let segments: Vec<Segment> = split_path(template).into_iter()
+                .map(|segment| {
+                    if segment.starts_with('{') && segment.ends_with('}') {
+                        Segment::Param
+                    } else {
+                        Segment::Literal(segment.to_string())
+                    }
+                })
+                .collect();
+            for method in HTTP_METHODS {
+                if let Some(operation) = item.get(*method) {
+                    routes.push(Route {
+                        method: method.to_uppercase(),
+                        template: template.clone(),
+                        segments: segments.clone(),
+                        operation: operation.clone(),
+                    });
+                }
+            }
+        }
+        // `/users/me` must win over `/users/{id}`
+        routes.sort_by_key(|route| std::cmp::Reverse(route.literals()));
+
+        // Requests may include the path of a server URL, e.g. `/v1`
+        let base_paths = document.get("servers").and_then(|s| s.as_array()).into_iter().flatten()
+            .filter_map(|server| server.get("url").and_then(|u| u.as_str()))
+            .map(|url| split_url(url).path.trim_end_matches('/').to_string())
+            .filter(|path| !path.is_empty() && path != "/")
+            .collect();
+
+        MockServer { document, routes, base_paths }
+    }
+
+    /// `METHOD /path` of every operation the mock answers.
+    pub fn operations(&self) -> Vec<String> {
+        self.routes.iter().map(|route| format!("{} {}", route.method, route.template)).collect()
+    }
+
+    fn find_route(&self, method: &str, path: &str) -> Result<&Route, MockResponse> {
+        let mut candidates = vec![path];
+        for base in &self.base_paths {
+            match path.strip_prefix(base.as_str()) {
+                Some(rest) if rest.is_empty() || rest.starts_with('/') => candidates.push(rest),
+                _ => {}
+            }
+        }
+        let mut path_matched = false;
+        for candidate in candidates {
+            let segments = split_path(candidate);
+            for route in self.routes.iter().filter(|route| route.matches(&segments)) {
+                if route.method == method {
+                    return Ok(route);
+                }
+                path_matched = true;
+            }
+        }
+        Err(if path_matched {
+            MockResponse::error(405, format!("{} is not defined for {}", method, path))
+        } else {
+            MockResponse::error(404, format!("No operation matches {} {}", method, path))
+        })
+    }
+
+    /// Picks the response for a request: the status asked for with `Prefer:
+    /// code=...` or `?__code=...`, otherwise the first 2xx response.
+    pub fn respond(&self, method: &str, url: &str, prefer: Option<&str>) -> MockResponse {
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        let path = percent_decode(path);
+        let query = parse_query_string(query);
+        let from_query = |key: &str| query.iter().find(|(name, _)| name == key).map(|(_, value)| value.as_str());
+
+        let route = match self.find_route(&method.to_uppercase(), &path) {
+            Ok(route) => route,
+            Err(response) => return response,
+        };
+        let Some(responses) = route.operation.get("responses").and_then(|r| r.as_object()) else {
+            return MockResponse::error(501, format!("{} {} has no responses", route.method, route.template));
+        };
+
+        let requested = from_query(CODE_PARAM).or_else(|| preference(prefer, "code"));
+        let chosen = match requested {
+            Some(code) => responses.iter()
+                .find(|(key, _)| *key == code)
+                .or_else(|| responses.iter().find(|(key, _)| covers(key, code)))
+                .or_else(|| responses.iter().find(|(key, _)| *key == "default"))
+                .map(|(_, response)| (code.to_string(), response)),
+            None => responses.iter()
+                .find(|(key, _)| key.starts_with('2'))
+                .or_else(|| responses.iter().next())
+                .map(|(key, response)| (key.clone(), response)),
+        };
+        let Some((code, response)) = chosen else {
+            return MockResponse::error(501, format!(
+                "{} {} has no {} response", route.method, route.template, requested.unwrap_or("2xx")
+            ));
+        };
+        let status = status_for(&code);
+
+        let content = response.get("content").and_then(|c| c.as_object());
+        let Some((content_type, media)) = content.and_then(|content| {
+            content.iter()
+                .find(|(content_type, _)| content_type.contains("json"))
+                .or_else(|| content.iter().next())
+        }) else {
+            return MockResponse { status, content_type: None, body: Vec::new(), source: "no content".to_string() };
+        };
+
+        let wanted_example = from_query(EXAMPLE_PARAM).or_else(|| preference(prefer, "example"));
+        let examples = media.get("examples").and_then(|e| e.as_object());
+        let named = |name: &str| examples.and_then(|e| e.get(name)).and_then(|e| e.get("value"));
+        let (value, source) = match (wanted_example.and_then(named), wanted_example) {
+            (Some(value), Some(name)) => (value.clone(), format!("example '{}'", name)),
+            _ => match (media.get("example"), examples.and_then(|e| e.iter().next())) {
+                (Some(example), _) => (example.clone(), "example".to_string()),
+                (None, Some((name, example))) => (
+                    example.get("value").cloned().unwrap_or(Value::Null),
+                    format!("example '{}'", name),
+                ),
+                (None, None) => (
+                    media.get("schema").map(|schema| sample_for_schema(schema, &self.document)).unwrap_or(Value::Null),
+                    "schema".to_string(),
+                ),
+            },
+        };
+
+        let body = match &value {
+            Value::String(text) if !content_type.contains("json") => text.clone().into_bytes(),
+            _ => serde_json::to_vec_pretty(&value).unwrap_or_default(),
+        };
+        MockResponse { status, content_type: Some(content_type.clone()), body, source }
+    }
+
+    /// Serves requests on `address` until the process is stopped, calling
+    /// `on_reply` with the method, URL and reply of each one.
+    pub fn serve(&self, address: &str, on_reply: impl Fn(&str, &str, &MockResponse)) -> io::Result<()> {
+        let server = Server::http(address).map_err(io::Error::other)?;
+        for request in server.incoming_requests() {
+            let method = request.method().as_str().to_string();
+            let url = request.url().to_string();
+            let prefer = request.headers().iter()
+                .find(|header| header.field.equiv("Prefer"))
+                .map(|header| header.value.as_str().to_string());
+
+            // Browsers send a preflight before cross-origin calls with custom headers
+            let reply = if method == "OPTIONS" && self.find_route("OPTIONS", &percent_decode(url.split('?').next().unwrap_or(""))).is_err() {
+                MockResponse { status: 204, content_type: None, body: Vec::new(), source: "CORS preflight".to_string() }
+            } else {
+                self.respond(&method, &url, prefer.as_deref())
+            };
+            on_reply(&method, &url, &reply);
+
+            let mut response = Response::from_data(reply.body.clone())
+                .with_status_code(reply.status)
+                .with_header(Header::from_bytes("Access-Control-Allow-Origin", "*").expect("valid header"))
+                .with_header(Header::from_bytes("Access-Control-Allow-Headers", "*").expect("valid header"))
+                .with_header(Header::from_bytes("Access-Control-Allow-Methods", "*").expect("valid header"));
+            if let Some(content_type) = &reply.content_type {
+                if let Ok(header) = Header::from_bytes("Content-Type", content_type.as_bytes()) {
+                    response = response.with_header(header);
+                }
+            }
+            // A client that went away doesn't stop the server
+            let _ = request.respond(response);
+        }
+        Ok(())
+    }
+}
@@ -1 +1,19 @@
-pub mod schema;
\ No newline at end of file
+pub mod schema;
+pub mod path;
+pub mod merge;
+pub mod update;
+pub mod validate;
+pub mod examples;
+pub mod diff;
+pub mod redact;
+pub mod sanitize;
+pub mod report;
+pub mod watch;
+pub mod preview;
+pub mod mock;
+pub mod docs;
+pub mod codegen;
+pub mod json_schema;
+pub mod contract;
+pub mod verify;
+pub mod scripts;
\ No newline at end of file
@@ -0,0 +1,152 @@
+pub struct UrlParts<'a> {
+    pub host: &'a str,
+    pub path: &'a str,
+    pub query: Option<&'a str>,
+}
+
+/// Splits an absolute or host-relative URL into host, path and raw query
+/// string, dropping the scheme and any fragment.
+pub fn split_url(url: &str) -> UrlParts<'_> {
+    let without_scheme = match url.find("://") {
+        Some(idx) => &url[idx + 3..],
+        None => url,
+    };
+    let without_fragment = without_scheme.split('#').next().unwrap_or("");
+    let (without_query, query) = match without_fragment.split_once('?') {
+        Some((rest, query)) => (rest, Some(query)),
+        None => (without_fragment, None),
+    };
+
+    match without_query.find('/') {
+        Some(idx) => UrlParts {
+            host: &without_query[..idx],
+            path: &without_query[idx..],
+            query,
+        },
+        None => UrlParts {
+            host: without_query,
+            path: "/",
+            query,
+        },
+    }
+}
+
+/// Decodes `%XX` escapes and, for query strings, `+` as a space.
+pub fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = |b: u8| (b as char).to_digit(16);
+                match (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                    (Some(high), Some(low)) => {
+                        decoded.push((high * 16 + low) as u8);
+                        i += 2;
+                    }
+                    _ => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Parses `a=1&b=2` into decoded key/value pairs, keeping their order.
+pub fn parse_query_string(query: &str) -> Vec<(String, String)> {
+    query.split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) => (percent_decode(key), percent_decode(value)),
+            None => (percent_decode(pair), String::new()),
+        })
+        .collect()
+}
+
+/// Splits a concrete request path into Postman-style segments, replacing
+/// segments that look like identifiers (numbers, UUIDs, long hex strings)
+/// with `:name` placeholders. Returns the segments and the collapsed
+/// `(name, value)` pairs so callers can record them as path variables.
+pub fn collapse_path_template(path: &str) -> (Vec<String>, Vec<(String, String)>) {
+    let mut segments = Vec::new();
+    let mut variables: Vec<(String, String)> = Vec::new();
+    let mut previous: Option<&str> = None;
+
+    for segment in path.split('/').filter(|s| !s.is_empty()) {
+        if is_identifier_segment(segment) {
+            let base = match previous {
+                Some(prev) => format!("{}Id", singularize(prev)),
+                None => "id".to_string(),
+            };
+            let mut name = base.clone();
+            let mut suffix = 2;
+            while variables.iter().any(|(n, _)| n == &name) {
+                name = format!("{}{}", base, suffix);
+                suffix += 1;
+            }
+            segments.push(format!(":{}", name));
+            variables.push((name, segment.to_string()));
+            previous = None;
+        } else {
+            segments.push(segment.to_string());
+            previous = Some(segment);
+        }
+    }
+
+    (segments, variables)
+}
+
+fn is_identifier_segment(segment: &str) -> bool {
+    if segment.chars().all(|c| c.is_ascii_digit()) {
+        return true;
+    }
+
+    if is_uuid(segment) {
+        return true;
+    }
+
+    segment.len() >= 16
+        && segment.chars().all(|c| c.is_ascii_hexdigit())
+        && segment.chars().any(|c| c.is_ascii_digit())
+}
+
+fn is_uuid(segment: &str) -> bool {
+    let groups: Vec<&str> = segment.split('-').collect();
+    groups.len() == 5
+        && groups.iter().map(|g| g.len()).eq([8, 4, 4, 4, 12])
+        && groups.iter().all(|g| g.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+fn singularize(word: &str) -> String {
+    let word: String = word
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .enumerate()
+        .map(|(i, part)| {
+            if i == 0 {
+                part.to_string()
+            } else {
+                let mut chars = part.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                    None => String::new(),
+                }
+            }
+        })
+        .collect();
+
+    if let Some(stem) = word.strip_suffix("ies") {
+        format!("{}y", stem)
+    } else if word.ends_with("ss") {
+        word
+    } else if let Some(stem) = word.strip_suffix('s') {
+        stem.to_string()
+    } else {
+        word
+    }
+}
\ No newline at end of file
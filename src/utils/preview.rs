@@ -0,0 +1,205 @@
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use tiny_http::{Header, Response, Server};
+
+use crate::models::openapi::load_openapi_document;
+use crate::utils::path::percent_decode;
+use crate::utils::watch::last_modified;
+
+/// Documentation viewer used by the preview page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreviewUi {
+    Swagger,
+    Redoc,
+}
+
+// The viewers are loaded from a CDN at exact versions, so a new release
+// can't change or break the page; the page itself is generated here.
+const SWAGGER_PAGE: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+  <meta charset="utf-8">
+  <title>API Preview</title>
+  <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@5.17.14/swagger-ui.css">
+</head>
+<body>
+  <div id="ui"></div>
+  <script src="https://unpkg.com/swagger-ui-dist@5.17.14/swagger-ui-bundle.js"></script>
+  <script src="https://unpkg.com/swagger-ui-dist@5.17.14/swagger-ui-standalone-preset.js"></script>
+  <script>
+    const specs = __SPECS__;
+    if (specs.length === 0) {
+      document.getElementById('ui').innerHTML = '<p>No specs found in __DIR__.</p>';
+    } else {
+      SwaggerUIBundle({
+        urls: specs.map(spec => ({ name: spec, url: '/specs/' + encodeURI(spec) })),
+        dom_id: '#ui',
+        presets: [SwaggerUIBundle.presets.apis, SwaggerUIStandalonePreset],
+        layout: 'StandaloneLayout',
+      });
+    }
+    __RELOAD__
+  </script>
+</body>
+</html>
+"#;
+
+const REDOC_PAGE: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+  <meta charset="utf-8">
+  <title>API Preview</title>
+  <style>body { margin: 0; } #bar { padding: 8px 16px; font-family: sans-serif; background: #263238; color: #fff; }</style>
+</head>
+<body>
+  <div id="bar">Spec: <select id="spec"></select></div>
+  <div id="redoc"></div>
+  <script src="https://unpkg.com/redoc@2.1.5/bundles/redoc.standalone.js"></script>
+  <script>
+    const specs = __SPECS__;
+    const select = document.getElementById('spec');
+    for (const spec of specs) {
+      select.add(new Option(spec, spec));
+    }
+    function show() {
+      history.replaceState(null, '', '?spec=' + encodeURIComponent(select.value));
+      Redoc.init('/specs/' + encodeURI(select.value), {}, document.getElementById('redoc'));
+    }
+    if (specs.length === 0) {
+      document.getElementById('redoc').innerHTML = '<p>No specs found in __DIR__.</p>';
+    } else {
+      const requested = new URLSearchParams(location.search).get('spec');
+      select.value = specs.includes(requested) ? requested : specs[0];
+      select.onchange = show;
+      show();
+    }
+    __RELOAD__
+  </script>
+</body>
+</html>
+"#;
+
+// Polls the server and reloads the page once the specs change.
+const RELOAD_SCRIPT: &str = r#"let version = null;
+    setInterval(() => fetch('/_version').then(r => r.text()).then(v => {
+      if (version !== null && v !== version) location.reload();
+      version = v;
+    }).catch(() => {}), 1000);"#;
+
+/// Serves the specs in a directory with a documentation page for browsing them.
+pub struct Preview {
+    dir: PathBuf,
+    ui: PreviewUi,
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn collect_specs(dir: &Path, root: &Path, specs: &mut Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+    paths.sort();
+    for path in paths {
+        let hidden = path.file_name()
+            .and_then(|s| s.to_str())
+            .is_some_and(|name| name.starts_with('.'));
+        if hidden {
+            continue;
+        }
+        if path.is_dir() {
+            collect_specs(&path, root, specs);
+            continue;
+        }
+        let extension = path.extension().and_then(|s| s.to_str());
+        if matches!(extension, Some("yaml" | "yml" | "json")) && load_openapi_document(&path).is_ok() {
+            if let Ok(relative) = path.strip_prefix(root) {
+                specs.push(relative.to_string_lossy().replace('\\', "/"));
+            }
+        }
+    }
+}
+
+impl Preview {
+    pub fn new(dir: PathBuf, ui: PreviewUi) -> Self {
+        Preview { dir, ui }
+    }
+
+    /// Paths, relative to the directory, of every OpenAPI document in it.
+    pub fn specs(&self) -> Vec<String> {
+        let mut specs = Vec::new();
+        collect_specs(&self.dir, &self.dir, &mut specs);
+        specs
+    }
+
+    /// Changes whenever a file in the directory is added, edited or removed.
+    fn version(&self) -> String {
+        last_modified(&self.dir)
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|elapsed| elapsed.as_nanos().to_string())
+            .unwrap_or_default()
+    }
+
+    fn page(&self) -> String {
+        let template = match self.ui {
+            PreviewUi::Swagger => SWAGGER_PAGE,
+            PreviewUi::Redoc => REDOC_PAGE,
+        };
+        let specs = serde_json::to_string(&self.specs())
+            .unwrap_or_else(|_| "[]".to_string())
+            .replace("</", "<\\/");
+        template
+            .replace("__SPECS__", &specs)
+            .replace("__DIR__", &escape_html(&self.dir.display().to_string()))
+            .replace("__RELOAD__", RELOAD_SCRIPT)
+    }
+
+    /// Reads a spec by its URL path, refusing anything outside the directory.
+    fn spec_file(&self, relative: &str) -> Option<(Vec<u8>, &'static str)> {
+        let relative = Path::new(relative);
+        if relative.components().any(|c| !matches!(c, Component::Normal(_))) {
+            return None;
+        }
+        let path = self.dir.join(relative);
+        let content_type = match path.extension().and_then(|s| s.to_str()) {
+            Some("json") => "application/json",
+            Some("yaml" | "yml") => "application/yaml",
+            _ => return None,
+        };
+        fs::read(&path).ok().map(|body| (body, content_type))
+    }
+
+    /// Answers one request with a status, content type and body.
+    pub fn handle(&self, url: &str) -> (u16, &'static str, Vec<u8>) {
+        let path = percent_decode(url.split('?').next().unwrap_or(""));
+        match path.as_str() {
+            "/" | "/index.html" => (200, "text/html; charset=utf-8", self.page().into_bytes()),
+            "/_version" => (200, "text/plain", self.version().into_bytes()),
+            "/specs.json" => {
+                let body = serde_json::to_vec(&self.specs()).unwrap_or_default();
+                (200, "application/json", body)
+            }
+            _ => match path.strip_prefix("/specs/").and_then(|relative| self.spec_file(relative)) {
+                Some((body, content_type)) => (200, content_type, body),
+                None => (404, "text/plain", b"Not found".to_vec()),
+            },
+        }
+    }
+
+    /// Serves requests on `address` until the process is stopped.
+    pub fn serve(&self, address: &str) -> io::Result<()> {
+        let server = Server::http(address).map_err(io::Error::other)?;
+        for request in server.incoming_requests() {
+            let (status, content_type, body) = self.handle(request.url());
+            let response = Response::from_data(body)
+                .with_status_code(status)
+                .with_header(Header::from_bytes("Content-Type", content_type).expect("valid header"))
+                .with_header(Header::from_bytes("Cache-Control", "no-store").expect("valid header"));
+            // A client that went away doesn't stop the server
+            let _ = request.respond(response);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,301 @@
+use regex::Regex;
+use serde_json::Value;
+
+use crate::models::postman::*;
+use crate::utils::path::percent_decode;
+
+pub const PLACEHOLDER: &str = "<redacted>";
+
+// Header names whose whole value is a credential.
+const SECRET_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-api-key",
+    "api-key",
+    "x-auth-token",
+    "x-access-token",
+    "x-csrf-token",
+    "x-xsrf-token",
+    "x-amz-security-token",
+];
+
+// Field, query and form names whose values are credentials. Matched
+// case-insensitively after removing `-` and `_`. Names are listed in full,
+// since a suffix such as `token` would also catch `nextPageToken`.
+const SECRET_KEYS: &[&str] = &[
+    "password",
+    "passwd",
+    "pwd",
+    "newpassword",
+    "oldpassword",
+    "currentpassword",
+    "passwordconfirmation",
+    "secret",
+    "clientsecret",
+    "apisecret",
+    "secretkey",
+    "token",
+    "accesstoken",
+    "refreshtoken",
+    "idtoken",
+    "authtoken",
+    "apitoken",
+    "sessiontoken",
+    "bearertoken",
+    "csrftoken",
+    "xsrftoken",
+    "apikey",
+    "privatekey",
+    "authorization",
+    "sessionid",
+    "signature",
+    "awssecretaccesskey",
+    "secretaccesskey",
+];
+
+// Values that are secrets wherever they appear.
+const BUILT_IN_PATTERNS: &[&str] = &[
+    // JSON Web Tokens
+    r"eyJ[A-Za-z0-9_-]{5,}\.eyJ[A-Za-z0-9_-]{5,}\.[A-Za-z0-9_-]*",
+    // AWS access key ids
+    r"\b(AKIA|ASIA)[0-9A-Z]{16}\b",
+    // Private keys
+    r"-----BEGIN [A-Z ]*PRIVATE KEY-----[\s\S]*?-----END [A-Z ]*PRIVATE KEY-----",
+];
+
+// Bearer / Basic credentials making up a whole header value. Only header
+// values are checked, since the words are common in prose ("basic plan").
+const AUTH_SCHEME_PATTERN: &str = r"^(?i)\s*(bearer|basic)\s+[A-Za-z0-9\-._~+/]{8,}=*\s*$";
+
+/// Replaces credentials in a collection with a placeholder before it is
+/// converted, so nothing sensitive reaches the generated spec.
+#[derive(Clone)]
+pub struct Redactor {
+    patterns: Vec<Regex>,
+    auth_scheme: Regex,
+    redacted: usize,
+}
+
+/// Lowercases a field name and drops `-` and `_`, so `api_key`, `Api-Key`
+/// and `apiKey` compare equal.
+pub(crate) fn normalize_key(key: &str) -> String {
+    key.chars()
+        .filter(|c| *c != '-' && *c != '_')
+        .collect::<String>()
+        .to_lowercase()
+}
+
+pub fn is_secret_key(key: &str) -> bool {
+    let key = normalize_key(key);
+    SECRET_KEYS.contains(&key.as_str())
+}
+
+fn is_secret_header(name: &str) -> bool {
+    SECRET_HEADERS.contains(&name.to_lowercase().as_str()) || is_secret_key(name)
+}
+
+/// Whether a raw body is `name=value&name=value` form data.
+fn is_urlencoded(body: &str) -> bool {
+    let body = body.trim();
+    !body.is_empty() && !body.contains(char::is_whitespace) && body.split('&').all(|pair| {
+        pair.split_once('=').is_some_and(|(key, _)| {
+            !key.is_empty() && key.chars().all(|c| c.is_alphanumeric() || "_-.[]%+".contains(c))
+        })
+    })
+}
+
+fn is_template(value: &str) -> bool {
+    // `{{token}}` is a Postman variable reference, not a secret
+    let value = value.trim();
+    value.starts_with("{{") && value.ends_with("}}")
+}
+
+impl Redactor {
+    /// Builds a redactor with the built-in detectors plus `extra_patterns`
+    /// (regular expressions whose matches are replaced).
+    pub fn new(extra_patterns: &[String]) -> Result<Self, regex::Error> {
+        let patterns = BUILT_IN_PATTERNS.iter()
+            .map(|p| p.to_string())
+            .chain(extra_patterns.iter().cloned())
+            .map(|p| Regex::new(&p))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let auth_scheme = Regex::new(AUTH_SCHEME_PATTERN)?;
+
+        Ok(Redactor { patterns, auth_scheme, redacted: 0 })
+    }
+
+    pub fn redact_text(&mut self, text: &str) -> String {
+        let mut result = text.to_string();
+        for pattern in &self.patterns {
+            if pattern.is_match(&result) {
+                self.redacted += pattern.find_iter(&result).count();
+                result = pattern.replace_all(&result, PLACEHOLDER).into_owned();
+            }
+        }
+        result
+    }
+
+    fn redact_secret(&mut self, value: &str) -> String {
+        if value.is_empty() || is_template(value) || value == PLACEHOLDER {
+            return value.to_string();
+        }
+        self.redacted += 1;
+        // Keep the auth scheme so the spec still shows how to authenticate
+        match value.split_once(' ') {
+            Some((scheme, _)) if ["bearer", "basic", "digest", "token"].contains(&scheme.to_lowercase().as_str()) => {
+                format!("{} {}", scheme, PLACEHOLDER)
+            }
+            _ => PLACEHOLDER.to_string(),
+        }
+    }
+
+    pub fn redact_json(&mut self, value: &mut Value) {
+        match value {
+            Value::Object(object) => {
+                for (key, child) in object.iter_mut() {
+                    match child {
+                        Value::String(text) if is_secret_key(key) => *text = self.redact_secret(text),
+                        // Keep the JSON type so the inferred schema doesn't change
+                        Value::Number(number) if is_secret_key(key) => {
+                            self.redacted += 1;
+                            *number = 0.into();
+                        }
+                        _ => self.redact_json(child),
+                    }
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    self.redact_json(item);
+                }
+            }
+            Value::String(s) => *s = self.redact_text(s),
+            _ => {}
+        }
+    }
+
+    /// Redacts a request or response body, keeping JSON bodies valid JSON
+    /// and form bodies valid `application/x-www-form-urlencoded`.
+    fn redact_body(&mut self, body: &str) -> String {
+        match serde_json::from_str::<Value>(body) {
+            Ok(mut json) if json.is_object() || json.is_array() => {
+                self.redact_json(&mut json);
+                serde_json::to_string_pretty(&json).unwrap_or_else(|_| body.to_string())
+            }
+            _ if is_urlencoded(body) => self.redact_urlencoded(body.trim()),
+            _ => self.redact_text(body),
+        }
+    }
+
+    /// Redacts `password=...&token=...` pairs by name, keeping the others'
+    /// encoding untouched.
+    fn redact_urlencoded(&mut self, body: &str) -> String {
+        body.split('&')
+            .map(|pair| match pair.split_once('=') {
+                Some((key, value)) if is_secret_key(&percent_decode(key)) => {
+                    format!("{}={}", key, self.redact_secret(&percent_decode(value)))
+                }
+                Some((key, value)) => format!("{}={}", key, self.redact_text(value)),
+                None => pair.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("&")
+    }
+
+    fn redact_headers(&mut self, headers: &mut [Header]) {
+        for header in headers {
+            let secret = header.key.as_deref().map(is_secret_header).unwrap_or(false);
+            if let Some(value) = header.value.as_mut() {
+                let secret = secret || self.auth_scheme.is_match(value);
+                *value = if secret { self.redact_secret(value) } else { self.redact_text(value) };
+            }
+        }
+    }
+
+    fn redact_pair(&mut self, key: Option<&str>, value: &mut Option<String>) {
+        let secret = key.map(is_secret_key).unwrap_or(false);
+        if let Some(value) = value.as_mut() {
+            *value = if secret { self.redact_secret(value) } else { self.redact_text(value) };
+        }
+    }
+
+    fn redact_request(&mut self, request: &mut Request) {
+        if let Some(headers) = request.header.as_mut() {
+            self.redact_headers(headers);
+        }
+        if let Some(queries) = request.url.as_mut().and_then(|u| u.query.as_mut()) {
+            for query in queries {
+                self.redact_pair(query.key.as_deref(), &mut query.value);
+            }
+        }
+        if let Some(body) = request.body.as_mut() {
+            if let Some(raw) = body.raw.as_mut() {
+                *raw = self.redact_body(raw);
+            }
+            for fields in [body.urlencoded.as_mut(), body.formdata.as_mut()].into_iter().flatten() {
+                for field in fields {
+                    self.redact_pair(field.key.as_deref(), &mut field.value);
+                }
+            }
+        }
+        if let Some(description) = request.description.as_mut() {
+            *description = self.redact_text(description);
+        }
+    }
+
+    fn redact_events(&mut self, events: &mut [Event]) {
+        for exec in events.iter_mut().filter_map(|e| e.script.as_mut()?.exec.as_mut()) {
+            match exec {
+                Exec::Lines(lines) => {
+                    for line in lines {
+                        *line = self.redact_text(line);
+                    }
+                }
+                Exec::Text(text) => *text = self.redact_text(text),
+            }
+        }
+    }
+
+    fn redact_items(&mut self, items: &mut [Item]) {
+        for item in items {
+            if let Some(events) = item.event.as_mut() {
+                self.redact_events(events);
+            }
+            if let Some(request) = item.request.as_mut() {
+                self.redact_request(request);
+            }
+            for response in item.response.iter_mut().flatten() {
+                if let Some(request) = response.original_request.as_mut() {
+                    self.redact_request(request);
+                }
+                if let Some(headers) = response.header.as_mut() {
+                    self.redact_headers(headers);
+                }
+                if let Some(body) = response.body.as_mut() {
+                    *body = self.redact_body(body);
+                }
+            }
+            if let Some(children) = item.item.as_mut() {
+                self.redact_items(children);
+            }
+        }
+    }
+
+    /// Redacts the collection in place and returns how many values were replaced.
+    pub fn redact_collection(&mut self, collection: &mut PostmanCollection) -> usize {
+        let before = self.redacted;
+        if let Some(description) = collection.info.as_mut().and_then(|i| i.description.as_mut()) {
+            *description = self.redact_text(description);
+        }
+        if let Some(events) = collection.event.as_mut() {
+            self.redact_events(events);
+        }
+        if let Some(items) = collection.item.as_mut() {
+            self.redact_items(items);
+        }
+        self.redacted - before
+    }
+}
\ No newline at end of file
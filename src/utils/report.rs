@@ -0,0 +1,109 @@
+use serde::Serialize;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileStatus {
+    /// Written to its own spec
+    Converted,
+    /// Collected for the combined spec
+    Merged,
+    /// Converted but not written because of validation errors
+    Skipped,
+    /// Could not be read or written
+    Failed,
+}
+
+impl FileStatus {
+    fn label(self) -> &'static str {
+        match self {
+            FileStatus::Converted => "ok",
+            FileStatus::Merged => "merged",
+            FileStatus::Skipped => "skipped",
+            FileStatus::Failed => "failed",
+        }
+    }
+}
+
+/// What happened to one input of a batch run.
+#[derive(Debug, Serialize)]
+pub struct FileSummary {
+    pub input: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+    pub status: FileStatus,
+    pub operations: usize,
+    pub warnings: usize,
+    pub errors: usize,
+    pub duration_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl FileSummary {
+    pub fn new(input: String) -> Self {
+        FileSummary {
+            input,
+            output: None,
+            status: FileStatus::Failed,
+            operations: 0,
+            warnings: 0,
+            errors: 0,
+            duration_ms: 0,
+            message: None,
+        }
+    }
+}
+
+/// End-of-run report for batch conversion.
+#[derive(Debug, Serialize)]
+pub struct RunSummary {
+    pub converted: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    pub operations: usize,
+    pub warnings: usize,
+    pub duration_ms: u64,
+    pub files: Vec<FileSummary>,
+}
+
+impl RunSummary {
+    pub fn new(files: Vec<FileSummary>, elapsed: Duration) -> Self {
+        let count = |status: FileStatus| files.iter().filter(|f| f.status == status).count();
+        RunSummary {
+            converted: count(FileStatus::Converted) + count(FileStatus::Merged),
+            failed: count(FileStatus::Failed),
+            skipped: count(FileStatus::Skipped),
+            operations: files.iter().map(|f| f.operations).sum(),
+            warnings: files.iter().map(|f| f.warnings).sum(),
+            duration_ms: elapsed.as_millis() as u64,
+            files,
+        }
+    }
+
+    pub fn to_text(&self) -> String {
+        let width = self.files.iter().map(|f| f.input.len()).max().unwrap_or(0);
+        let mut out = String::new();
+        for file in &self.files {
+            out.push_str(&format!("  {:<8}{:<width$}", file.status.label(), file.input, width = width));
+            match (&file.message, file.status) {
+                (Some(message), FileStatus::Failed) => out.push_str(&format!("  {}", message)),
+                _ => out.push_str(&format!(
+                    "  {:>4} operation(s)  {:>3} warning(s)  {:>3} error(s)  {:>6} ms",
+                    file.operations, file.warnings, file.errors, file.duration_ms
+                )),
+            }
+            out.push('\n');
+        }
+        out.push_str(&format!(
+            "{} converted, {} skipped, {} failed; {} operation(s), {} warning(s) in {:.2}s\n",
+            self.converted, self.skipped, self.failed, self.operations, self.warnings,
+            self.duration_ms as f64 / 1000.0
+        ));
+        out
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
@@ -0,0 +1,306 @@
+use std::fs;
+use std::path::Path;
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::error::ConversionError;
+use crate::models::openapi::*;
+use crate::utils::redact::normalize_key;
+
+/// Rules for cleaning up saved examples, read from a TOML file:
+///
+/// ```toml
+/// mask = "<masked>"
+/// fields = ["email", "phone", "first_name"]
+/// max_array_items = 3
+/// max_string_length = 200
+/// max_example_bytes = 16384
+///
+/// [[patterns]]
+/// pattern = "\\b\\d{3}-\\d{2}-\\d{4}\\b"
+/// replacement = "000-00-0000"
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SanitizeConfig {
+    /// Replacement for string values of PII fields that have no recognizable
+    /// shape (emails, dates and digit strings keep theirs)
+    pub mask: String,
+    /// Field names whose values are masked, matched case-insensitively
+    /// ignoring `-` and `_`; replaces the built-in list when set
+    pub fields: Option<Vec<String>>,
+    /// Field names masked in addition to `fields` or the built-in list
+    pub extra_fields: Vec<String>,
+    /// Value patterns replaced wherever they occur in strings; replaces the
+    /// built-in patterns when set
+    pub patterns: Option<Vec<PatternRule>>,
+    /// Longest array kept in an example
+    pub max_array_items: Option<usize>,
+    /// Longest string kept in an example, in characters
+    pub max_string_length: Option<usize>,
+    /// Largest example kept, in bytes of compact JSON
+    pub max_example_bytes: Option<usize>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PatternRule {
+    pub pattern: String,
+    pub replacement: Option<String>,
+}
+
+const DEFAULT_FIELDS: &[&str] = &[
+    "email",
+    "emailaddress",
+    "phone",
+    "phonenumber",
+    "mobile",
+    "firstname",
+    "lastname",
+    "fullname",
+    "surname",
+    "street",
+    "streetaddress",
+    "homeaddress",
+    "postcode",
+    "zipcode",
+    "birthdate",
+    "dateofbirth",
+    "dob",
+    "ssn",
+    "iban",
+    "cardnumber",
+    "ipaddress",
+];
+
+const DEFAULT_PATTERNS: &[(&str, &str)] = &[
+    (r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}", "user@example.com"),
+    (r"\b(?:\d[ -]?){13,16}\b", "0000 0000 0000 0000"),
+    (r"\b\d{1,3}\.\d{1,3}\.\d{1,3}\.\d{1,3}\b", "192.0.2.0"),
+];
+
+impl Default for SanitizeConfig {
+    fn default() -> Self {
+        SanitizeConfig {
+            mask: "<masked>".to_string(),
+            fields: None,
+            extra_fields: Vec::new(),
+            patterns: None,
+            max_array_items: Some(5),
+            max_string_length: Some(500),
+            max_example_bytes: Some(64 * 1024),
+        }
+    }
+}
+
+impl SanitizeConfig {
+    pub fn load(config_file: &Path) -> Result<Self, ConversionError> {
+        let contents = fs::read_to_string(config_file)
+            .map_err(ConversionError::FileError)?;
+        toml::from_str(&contents)
+            .map_err(|e| ConversionError::InvalidFormat(format!("{}: {}", config_file.display(), e)))
+    }
+}
+
+/// Counts of what a sanitizing pass changed.
+#[derive(Debug, Default)]
+pub struct SanitizeReport {
+    pub masked: usize,
+    pub truncated: usize,
+    pub dropped: usize,
+}
+
+/// Applies a `SanitizeConfig` to the examples of a converted spec.
+pub struct Sanitizer {
+    mask: String,
+    fields: Vec<String>,
+    patterns: Vec<(Regex, String)>,
+    max_array_items: Option<usize>,
+    max_string_length: Option<usize>,
+    max_example_bytes: Option<usize>,
+}
+
+impl Sanitizer {
+    pub fn new(config: &SanitizeConfig) -> Result<Self, regex::Error> {
+        let fields = match &config.fields {
+            Some(fields) => fields.iter().map(|f| normalize_key(f)).collect(),
+            None => DEFAULT_FIELDS.iter().map(|f| f.to_string()).collect::<Vec<_>>(),
+        }
+        .into_iter()
+        .chain(config.extra_fields.iter().map(|f| normalize_key(f)))
+        .collect();
+
+        let patterns = match &config.patterns {
+            Some(rules) => rules.iter()
+                .map(|rule| Ok((Regex::new(&rule.pattern)?, rule.replacement.clone().unwrap_or_else(|| config.mask.clone()))))
+                .collect::<Result<Vec<_>, regex::Error>>()?,
+            None => DEFAULT_PATTERNS.iter()
+                .map(|(pattern, replacement)| Ok((Regex::new(pattern)?, replacement.to_string())))
+                .collect::<Result<Vec<_>, regex::Error>>()?,
+        };
+
+        Ok(Sanitizer {
+            mask: config.mask.clone(),
+            fields,
+            patterns,
+            max_array_items: config.max_array_items,
+            max_string_length: config.max_string_length,
+            max_example_bytes: config.max_example_bytes,
+        })
+    }
+
+    fn is_pii_field(&self, key: &str) -> bool {
+        self.fields.contains(&normalize_key(key))
+    }
+
+    /// A stand-in for a PII value with the same JSON type and shape, so the
+    /// example still matches the schema inferred from it.
+    fn mask_value(&self, value: &Value) -> Value {
+        match value {
+            Value::Number(n) if n.is_f64() => serde_json::json!(0.0),
+            Value::Number(_) => Value::from(0),
+            Value::String(s) => Value::String(self.mask_string(s)),
+            other => other.clone(),
+        }
+    }
+
+    fn mask_string(&self, s: &str) -> String {
+        let is_date = |s: &str| s.len() == 10 && s.chars().enumerate().all(|(i, c)| if i == 4 || i == 7 { c == '-' } else { c.is_ascii_digit() });
+        if s.contains('@') && s.split_once('@').is_some_and(|(user, domain)| !user.is_empty() && domain.contains('.')) {
+            "user@example.com".to_string()
+        } else if is_date(s) {
+            "1970-01-01".to_string()
+        } else if s.len() > 10 && is_date(&s[..10]) && s[10..].starts_with('T') {
+            "1970-01-01T00:00:00Z".to_string()
+        } else if s.chars().any(|c| c.is_ascii_digit())
+            && s.chars().all(|c| c.is_ascii_digit() || " +-().".contains(c)) {
+            // Phone numbers, postcodes, card numbers: keep the layout
+            s.chars().map(|c| if c.is_ascii_digit() { '0' } else { c }).collect()
+        } else {
+            self.mask.clone()
+        }
+    }
+
+    fn sanitize_value(&self, value: &mut Value, max_array_items: Option<usize>, report: &mut SanitizeReport) {
+        match value {
+            Value::Object(object) => {
+                for (key, child) in object.iter_mut() {
+                    if self.is_pii_field(key) && !child.is_null() && !child.is_object() && !child.is_array() {
+                        let masked = self.mask_value(child);
+                        if *child != masked {
+                            *child = masked;
+                            report.masked += 1;
+                        }
+                    } else {
+                        self.sanitize_value(child, max_array_items, report);
+                    }
+                }
+            }
+            Value::Array(items) => {
+                if let Some(max) = max_array_items {
+                    if items.len() > max {
+                        items.truncate(max);
+                        report.truncated += 1;
+                    }
+                }
+                for item in items {
+                    self.sanitize_value(item, max_array_items, report);
+                }
+            }
+            Value::String(s) => {
+                for (pattern, replacement) in &self.patterns {
+                    if pattern.is_match(s) {
+                        report.masked += pattern.find_iter(s).count();
+                        *s = pattern.replace_all(s, replacement.as_str()).into_owned();
+                    }
+                }
+                if let Some(max) = self.max_string_length {
+                    if s.chars().count() > max {
+                        *s = s.chars().take(max).collect::<String>() + "...";
+                        report.truncated += 1;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Sanitizes one example; returns false when it is still over the size
+    /// cap after shrinking its arrays and should be dropped.
+    fn sanitize_example(&self, example: &mut Value, report: &mut SanitizeReport) -> bool {
+        self.sanitize_value(example, self.max_array_items, report);
+
+        let Some(max_bytes) = self.max_example_bytes else { return true };
+        let size = |value: &Value| serde_json::to_string(value).map(|s| s.len()).unwrap_or(0);
+
+        // Halve the longest array until the example fits or arrays are down to one item
+        let mut max_items = longest_array(example);
+        while size(example) > max_bytes && max_items > 1 {
+            max_items = (max_items / 2).max(1);
+            if truncate_arrays(example, max_items) {
+                report.truncated += 1;
+            }
+        }
+
+        if size(example) > max_bytes {
+            report.dropped += 1;
+            return false;
+        }
+        true
+    }
+
+    fn sanitize_content(&self, content: &mut Content, report: &mut SanitizeReport) {
+        if let Some(example) = content.example.as_mut() {
+            if !self.sanitize_example(example, report) {
+                content.example = None;
+            }
+        }
+        if let Some(examples) = content.examples.as_mut() {
+            examples.retain(|_, example| self.sanitize_example(&mut example.value, report));
+        }
+    }
+
+    /// Masks and truncates every request and response example in the spec.
+    pub fn sanitize_spec(&self, spec: &mut OpenAPISpec) -> SanitizeReport {
+        let mut report = SanitizeReport::default();
+        for operations in spec.paths.values_mut() {
+            for operation in operations.values_mut() {
+                if let Some(body) = operation.request_body.as_mut() {
+                    for content in body.content.values_mut() {
+                        self.sanitize_content(content, &mut report);
+                    }
+                }
+                for response in operation.responses.values_mut() {
+                    for content in response.content.values_mut() {
+                        self.sanitize_content(content, &mut report);
+                    }
+                }
+            }
+        }
+        report
+    }
+}
+
+fn longest_array(value: &Value) -> usize {
+    match value {
+        Value::Array(items) => items.iter().map(longest_array).max().unwrap_or(0).max(items.len()),
+        Value::Object(object) => object.values().map(longest_array).max().unwrap_or(0),
+        _ => 0,
+    }
+}
+
+fn truncate_arrays(value: &mut Value, max_items: usize) -> bool {
+    match value {
+        Value::Array(items) => {
+            let mut truncated = items.len() > max_items;
+            items.truncate(max_items);
+            for item in items {
+                truncated |= truncate_arrays(item, max_items);
+            }
+            truncated
+        }
+        Value::Object(object) => object.values_mut().fold(false, |acc, child| truncate_arrays(child, max_items) | acc),
+        _ => false,
+    }
+}
\ No newline at end of file
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use indexmap::IndexMap;
 
 use serde_json::Value;
 use crate::models::openapi::Schema;
@@ -6,7 +6,7 @@ use crate::models::openapi::Schema;
 pub fn infer_schema(data: &Value) -> Schema {
     match data {
         Value::Object(obj) => {
-            let mut properties = HashMap::new();
+            let mut properties = IndexMap::new();
             let mut required = Vec::new();
             
             for (key, value) in obj {
@@ -21,6 +21,7 @@ pub fn infer_schema(data: &Value) -> Schema {
                 properties: Some(properties),
                 required: if required.is_empty() { None } else { Some(required) },
                 items: None,
+                keywords: IndexMap::new(),
             }
         },
         Value::Array(arr) => {
@@ -30,6 +31,7 @@ pub fn infer_schema(data: &Value) -> Schema {
                     properties: None,
                     required: None,
                     items: Some(Box::new(infer_schema(first))),
+                    keywords: IndexMap::new(),
                 }
             } else {
                 Schema {
@@ -41,7 +43,9 @@ pub fn infer_schema(data: &Value) -> Schema {
                         properties: None,
                         required: None,
                         items: None,
+                        keywords: IndexMap::new(),
                     })),
+                    keywords: IndexMap::new(),
                 }
             }
         },
@@ -50,24 +54,28 @@ pub fn infer_schema(data: &Value) -> Schema {
             properties: None,
             required: None,
             items: None,
+            keywords: IndexMap::new(),
         },
         Value::Number(_) => Schema {
             schema_type: "number".to_string(),
             properties: None,
             required: None,
             items: None,
+            keywords: IndexMap::new(),
         },
         Value::String(_) => Schema {
             schema_type: "string".to_string(),
             properties: None,
             required: None,
             items: None,
+            keywords: IndexMap::new(),
         },
         _ => Schema {
             schema_type: "string".to_string(),
             properties: None,
             required: None,
             items: None,
+            keywords: IndexMap::new(),
         },
     }
 }
\ No newline at end of file
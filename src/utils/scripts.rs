@@ -0,0 +1,405 @@
+//! Reads facts out of Postman test scripts without running them: the
+//! literal schemas passed to schema assertions and the status codes the
+//! response is expected to have.
+
+use regex::Regex;
+use serde_json::{Map, Number, Value};
+
+// Guards against variables defined in terms of each other.
+const MAX_DEPTH: usize = 16;
+
+/// Assertions taking a schema, and which argument it is.
+const SCHEMA_CALLS: &[(&str, usize)] = &[
+    ("to.have.jsonSchema(", 0),
+    ("tv4.validate(", 1),
+    ("tv4.validateResult(", 1),
+    ("tv4.validateMultiple(", 1),
+    ("ajv.validate(", 0),
+    ("ajv.compile(", 0),
+];
+
+/// Parses JavaScript literals (objects, arrays, strings, numbers, booleans
+/// and `null`) as JSON, allowing unquoted keys, single quotes, trailing
+/// commas and comments. Identifiers are looked up among the script's
+/// variables, so a schema may be split over several `const`s.
+struct Parser<'a> {
+    chars: Vec<char>,
+    pos: usize,
+    script: &'a str,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &str, script: &'a str, depth: usize) -> Self {
+        Parser { chars: text.chars().collect(), pos: 0, script, depth }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_space(&mut self) {
+        loop {
+            match (self.peek(), self.chars.get(self.pos + 1)) {
+                (Some(c), _) if c.is_whitespace() => self.pos += 1,
+                (Some('/'), Some('/')) => {
+                    while self.peek().is_some_and(|c| c != '\n') {
+                        self.pos += 1;
+                    }
+                }
+                (Some('/'), Some('*')) => {
+                    self.pos += 2;
+                    while self.pos < self.chars.len() && !(self.peek() == Some('*') && self.chars.get(self.pos + 1) == Some(&'/')) {
+                        self.pos += 1;
+                    }
+                    self.pos += 2;
+                }
+                _ => return,
+            }
+        }
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        self.skip_space();
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn identifier(&mut self) -> Option<String> {
+        self.skip_space();
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '$') {
+            self.pos += 1;
+        }
+        (self.pos > start).then(|| self.chars[start..self.pos].iter().collect())
+    }
+
+    fn string(&mut self) -> Option<String> {
+        let quote = self.peek()?;
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            let c = self.peek()?;
+            self.pos += 1;
+            match c {
+                '\\' => {
+                    let escaped = self.peek()?;
+                    self.pos += 1;
+                    match escaped {
+                        'n' => out.push('\n'),
+                        't' => out.push('\t'),
+                        'r' => out.push('\r'),
+                        'u' => {
+                            let code: String = self.chars.get(self.pos..self.pos + 4)?.iter().collect();
+                            self.pos += 4;
+                            out.push(char::from_u32(u32::from_str_radix(&code, 16).ok()?)?);
+                        }
+                        other => out.push(other),
+                    }
+                }
+                // Template literals with substitutions aren't literals
+                '$' if quote == '`' && self.peek() == Some('{') => return None,
+                c if c == quote => return Some(out),
+                c => out.push(c),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Option<Value> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E')) {
+            self.pos += 1;
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        match text.parse::<i64>() {
+            Ok(n) => Some(Value::Number(n.into())),
+            Err(_) => text.parse::<f64>().ok().and_then(Number::from_f64).map(Value::Number),
+        }
+    }
+
+    fn value(&mut self) -> Option<Value> {
+        self.skip_space();
+        match self.peek()? {
+            '{' => self.object(),
+            '[' => self.array(),
+            '"' | '\'' | '`' => self.string().map(Value::String),
+            c if c.is_ascii_digit() || c == '-' => self.number(),
+            _ => match self.identifier()?.as_str() {
+                "true" => Some(Value::Bool(true)),
+                "false" => Some(Value::Bool(false)),
+                "null" => Some(Value::Null),
+                name => variable(self.script, name, self.depth + 1),
+            },
+        }
+    }
+
+    fn object(&mut self) -> Option<Value> {
+        self.pos += 1;
+        let mut object = Map::new();
+        loop {
+            if self.eat('}') {
+                return Some(Value::Object(object));
+            }
+            self.skip_space();
+            let key = match self.peek()? {
+                '"' | '\'' => self.string()?,
+                _ => self.identifier()?,
+            };
+            if !self.eat(':') {
+                return None;
+            }
+            let value = self.value()?;
+            object.insert(key, value);
+            if !self.eat(',') {
+                return self.eat('}').then_some(Value::Object(object));
+            }
+        }
+    }
+
+    fn array(&mut self) -> Option<Value> {
+        self.pos += 1;
+        let mut items = Vec::new();
+        loop {
+            if self.eat(']') {
+                return Some(Value::Array(items));
+            }
+            items.push(self.value()?);
+            if !self.eat(',') {
+                return self.eat(']').then_some(Value::Array(items));
+            }
+        }
+    }
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+/// Parses a literal at the start of `source`, refusing expressions that
+/// merely begin with one, such as `schemas.user` or `build({...})`.
+fn literal(source: &str, script: &str, depth: usize) -> Option<Value> {
+    let mut parser = Parser::new(source, script, depth);
+    let value = parser.value()?;
+    // Statements may end at a line break instead of a semicolon
+    while matches!(parser.peek(), Some(' ' | '\t')) {
+        parser.pos += 1;
+    }
+    match parser.peek() {
+        None | Some(';' | ',' | ')' | '\n' | '\r' | '/') => Some(value),
+        _ => None,
+    }
+}
+
+/// The literal a `var`, `let` or `const` named `name` is set to.
+fn variable(script: &str, name: &str, depth: usize) -> Option<Value> {
+    if depth > MAX_DEPTH {
+        return None;
+    }
+    for keyword in ["const ", "let ", "var "] {
+        let mut rest = script;
+        while let Some(found) = rest.find(keyword) {
+            let preceded = rest[..found].chars().next_back().is_some_and(is_identifier_char);
+            let after = rest[found + keyword.len()..].trim_start();
+            rest = &rest[found + keyword.len()..];
+            if preceded {
+                continue;
+            }
+            let Some(tail) = after.strip_prefix(name) else { continue };
+            if tail.starts_with(is_identifier_char) {
+                continue;
+            }
+            if let Some(initializer) = tail.trim_start().strip_prefix('=') {
+                return literal(initializer, script, depth);
+            }
+        }
+    }
+    None
+}
+
+/// Source text of the `index`-th argument of the call whose `(` ends at
+/// `start`.
+fn argument(script: &str, start: usize, index: usize) -> Option<&str> {
+    let mut depth = 0;
+    let mut quote = None;
+    let mut current = 0;
+    let mut arg_start = start;
+    let mut escaped = false;
+    for (offset, c) in script[start..].char_indices() {
+        let at = start + offset;
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' | '`' => quote = Some(c),
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' if depth > 0 => depth -= 1,
+            ')' => return (current == index).then(|| script[arg_start..at].trim()),
+            ',' if depth == 0 => {
+                if current == index {
+                    return Some(script[arg_start..at].trim());
+                }
+                current += 1;
+                arg_start = at + 1;
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// A schema a test script checks the response body against.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaAssertion {
+    /// Status the schema is checked under, when the script makes it clear
+    pub status: Option<u16>,
+    pub schema: Value,
+}
+
+/// Byte ranges of the `{...}` blocks in `script`, skipping braces in strings.
+fn blocks(script: &str) -> Vec<(usize, usize)> {
+    let mut open = Vec::new();
+    let mut found = Vec::new();
+    let mut quote = None;
+    let mut escaped = false;
+    for (at, c) in script.char_indices() {
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' | '`' => quote = Some(c),
+            '{' => open.push(at),
+            '}' => {
+                if let Some(start) = open.pop() {
+                    found.push((start, at));
+                }
+            }
+            _ => {}
+        }
+    }
+    found
+}
+
+/// The status a schema checked at `position` belongs to: the one status
+/// asserted in the innermost enclosing block (or the whole script) that
+/// asserts any. `None` when that scope asserts several. A block includes
+/// its header, so `if (pm.response.code === 404) { ... }` counts.
+fn status_at(script: &str, position: usize, blocks: &[(usize, usize)], statuses: &[(usize, u16)]) -> Option<u16> {
+    let mut scopes: Vec<(usize, usize)> = blocks.iter()
+        .copied()
+        .filter(|(start, end)| *start < position && position < *end)
+        .map(|(start, end)| (script[..start].rfind([';', '{', '}']).map_or(0, |i| i + 1), end))
+        .collect();
+    scopes.sort_by_key(|(start, end)| end - start);
+    scopes.push((0, script.len()));
+    for (start, end) in scopes {
+        let mut codes: Vec<u16> = statuses.iter()
+            .filter(|(at, _)| (start..end).contains(at))
+            .map(|(_, code)| *code)
+            .collect();
+        codes.sort_unstable();
+        codes.dedup();
+        match codes.as_slice() {
+            [] => continue,
+            [code] => return Some(*code),
+            _ => return None,
+        }
+    }
+    None
+}
+
+/// Schemas passed as literals, or as variables holding literals, to
+/// `pm.response.to.have.jsonSchema(...)`, `tv4.validate(data, schema)` or
+/// `ajv.validate(schema, data)`, in the order they appear, with the status
+/// each is checked under when it can be told.
+pub fn schema_assertions(script: &str) -> Vec<SchemaAssertion> {
+    let mut found: Vec<(usize, Value)> = Vec::new();
+    for (call, index) in SCHEMA_CALLS {
+        let mut from = 0;
+        while let Some(position) = script[from..].find(call) {
+            let start = from + position + call.len();
+            from = start;
+            let Some(source) = argument(script, start, *index) else { continue };
+            if let Some(schema) = literal(source, script, 0).filter(|s| s.is_object()) {
+                found.push((start, schema));
+            }
+        }
+    }
+    found.sort_by_key(|(position, _)| *position);
+
+    let blocks = blocks(script);
+    let statuses = status_positions(script);
+    found.into_iter()
+        .map(|(position, schema)| SchemaAssertion {
+            status: status_at(script, position, &blocks, &statuses),
+            schema,
+        })
+        .collect()
+}
+
+/// Status codes asserted with `pm.response.to.have.status(201)`,
+/// `pm.expect(pm.response.code).to.eql(204)`, `.to.be.oneOf([200, 201])`
+/// or `responseCode.code === 200`, in the order they appear. Negated
+/// assertions and codes held in expressions are skipped.
+pub fn status_assertions(script: &str) -> Vec<u16> {
+    let mut seen = Vec::new();
+    for (_, code) in status_positions(script) {
+        if !seen.contains(&code) {
+            seen.push(code);
+        }
+    }
+    seen
+}
+
+/// Every asserted status code with where its assertion starts, in order.
+fn status_positions(script: &str) -> Vec<(usize, u16)> {
+    let have_status = Regex::new(r"\.to\.have\.status\(\s*(\d{3})\s*\)").unwrap();
+    let expect_code = Regex::new(
+        r"(?:pm\.response\.code|responseCode\.code)\s*\)(?:\s*\.\s*(?:to|be|is|that|and|deep))*\s*\.\s*(?:eql|equal|equals|eq|oneOf)\s*\(",
+    ).unwrap();
+    let compare_code = Regex::new(r"(?:pm\.response\.code|responseCode\.code)\s*===?\s*(\d{3})\b").unwrap();
+
+    let mut found: Vec<(usize, u16)> = Vec::new();
+    let mut push = |position: usize, value: &Value| {
+        let codes = match value {
+            Value::Array(items) => items.iter().collect(),
+            single => vec![single],
+        };
+        for code in codes {
+            if let Some(code) = code.as_u64().filter(|c| (100..600).contains(c)) {
+                found.push((position, code as u16));
+            }
+        }
+    };
+    for captures in have_status.captures_iter(script).chain(compare_code.captures_iter(script)) {
+        if let (Some(call), Ok(code)) = (captures.get(0), captures[1].parse::<u64>()) {
+            push(call.start(), &Value::from(code));
+        }
+    }
+    for call in expect_code.find_iter(script) {
+        let Some(source) = argument(script, call.end(), 0) else { continue };
+        if let Some(value) = literal(source, script, 0) {
+            push(call.start(), &value);
+        }
+    }
+
+    found.sort_by_key(|(position, _)| *position);
+    found
+}
@@ -0,0 +1,290 @@
+use serde_json::{Map, Value};
+
+use crate::models::openapi::HTTP_METHODS;
+
+/// What an update changed, for printing after the spec is written.
+#[derive(Debug, Default)]
+pub struct UpdateReport {
+    /// `METHOD /path` of operations that were not in the existing spec
+    pub added: Vec<String>,
+    /// `METHOD /path` of operations no longer produced by the collection
+    pub removed: Vec<String>,
+    /// Number of hand-edited values kept over a newly generated one
+    pub kept_edits: usize,
+}
+
+// Marks operations that disappeared from the collection. They are deprecated
+// instead of deleted so a reviewer can decide what to do with them.
+const REMOVED_MARKER: &str = "x-removed-from-collection";
+
+/// Keys people add or edit by hand after generation. Without a base snapshot
+/// these always keep their existing value.
+fn is_hand_edited_key(key: &str) -> bool {
+    key.starts_with("x-") || matches!(
+        key,
+        "description"
+            | "enum"
+            | "format"
+            | "default"
+            | "nullable"
+            | "deprecated"
+            | "pattern"
+            | "minimum"
+            | "maximum"
+            | "exclusiveMinimum"
+            | "exclusiveMaximum"
+            | "minLength"
+            | "maxLength"
+            | "minItems"
+            | "maxItems"
+            | "uniqueItems"
+    )
+}
+
+/// Keywords whose value is a map keyed by names the API chose (property
+/// names, status codes, media types, ...), so its keys are never keywords.
+const NAME_MAPS: &[&str] = &[
+    "properties", "patternProperties", "responses", "content", "examples", "schemas",
+    "headers", "links", "callbacks", "encoding", "variables", "securitySchemes", "$defs",
+];
+
+/// Three-way merges a freshly generated spec into an existing, possibly
+/// hand-edited one. `base` is the spec generated last time; when it is
+/// available a value is only overwritten if the human left it untouched,
+/// otherwise hand-edited keys (descriptions, `enum`, `format`, `x-*`, ...)
+/// win and everything else follows the new spec.
+pub fn update_document(existing: &Value, base: Option<&Value>, generated: &Value) -> (Value, UpdateReport) {
+    let mut report = UpdateReport::default();
+    let mut result = existing.as_object().cloned().unwrap_or_default();
+    let empty = Value::Null;
+
+    if let Some(info) = generated.get("info") {
+        let merged = match existing.get("info") {
+            Some(current) => merge_value("info", false, current, child(base, "info"), info, &mut report),
+            None => info.clone(),
+        };
+        result.insert("info".to_string(), merged);
+    }
+
+    let paths = update_paths(
+        existing.get("paths").unwrap_or(&empty),
+        child(base, "paths"),
+        generated.get("paths").unwrap_or(&empty),
+        &mut report,
+    );
+    result.insert("paths".to_string(), Value::Object(paths));
+
+    // Schemas are only ever added or updated, never dropped
+    let existing_schemas = existing.pointer("/components/schemas").and_then(|s| s.as_object());
+    let base_schemas = base.and_then(|b| b.pointer("/components/schemas"));
+    let mut schemas = existing_schemas.cloned().unwrap_or_default();
+    if let Some(generated_schemas) = generated.pointer("/components/schemas").and_then(|s| s.as_object()) {
+        for (name, schema) in generated_schemas {
+            let merged = match schemas.get(name) {
+                Some(current) => merge_value(name, true, current, child(base_schemas, name), schema, &mut report),
+                None => schema.clone(),
+            };
+            schemas.insert(name.clone(), merged);
+        }
+    }
+    let components = result.entry("components".to_string())
+        .or_insert_with(|| Value::Object(Map::new()));
+    if let Some(components) = components.as_object_mut() {
+        components.insert("schemas".to_string(), Value::Object(schemas));
+    }
+
+    // Tags are matched by name so hand-written tag descriptions survive
+    let mut tags: Vec<Value> = existing.get("tags").and_then(|t| t.as_array()).cloned().unwrap_or_default();
+    if let Some(generated_tags) = generated.get("tags").and_then(|t| t.as_array()) {
+        for tag in generated_tags {
+            if !tags.iter().any(|t| t.get("name") == tag.get("name")) {
+                tags.push(tag.clone());
+            }
+        }
+    }
+    result.insert("tags".to_string(), Value::Array(tags));
+
+    for (key, value) in generated.as_object().into_iter().flatten() {
+        if !result.contains_key(key) {
+            result.insert(key.clone(), value.clone());
+        }
+    }
+
+    (Value::Object(result), report)
+}
+
+fn child<'a>(value: Option<&'a Value>, key: &str) -> Option<&'a Value> {
+    value.and_then(|v| v.get(key))
+}
+
+fn update_paths(existing: &Value, base: Option<&Value>, generated: &Value, report: &mut UpdateReport) -> Map<String, Value> {
+    let mut paths = existing.as_object().cloned().unwrap_or_default();
+    let generated = generated.as_object().cloned().unwrap_or_default();
+
+    // Flag operations the collection no longer produces
+    for (path, operations) in paths.iter_mut() {
+        let Some(operations) = operations.as_object_mut() else { continue };
+        for (method, operation) in operations.iter_mut() {
+            if !HTTP_METHODS.contains(&method.as_str()) {
+                continue;
+            }
+            let still_generated = generated.get(path).and_then(|ops| ops.get(method)).is_some();
+            let Some(operation) = operation.as_object_mut() else { continue };
+            if !still_generated && !operation.contains_key(REMOVED_MARKER) {
+                operation.insert("deprecated".to_string(), Value::Bool(true));
+                operation.insert(REMOVED_MARKER.to_string(), Value::Bool(true));
+                report.removed.push(format!("{} {}", method.to_uppercase(), path));
+            }
+        }
+    }
+
+    for (path, operations) in generated {
+        let Some(operations) = operations.as_object() else { continue };
+        let path_item = paths.entry(path.clone())
+            .or_insert_with(|| Value::Object(Map::new()));
+        let Some(path_item) = path_item.as_object_mut() else { continue };
+
+        for (method, operation) in operations {
+            let merged = match path_item.get(method) {
+                Some(current) => {
+                    let base_operation = child(child(base, &path), method);
+                    let mut merged = merge_value(method, false, current, base_operation, operation, report);
+                    // The operation is back in the collection
+                    if let Some(merged) = merged.as_object_mut() {
+                        if merged.remove(REMOVED_MARKER).is_some() {
+                            merged.remove("deprecated");
+                        }
+                    }
+                    merged
+                }
+                None => {
+                    report.added.push(format!("{} {}", method.to_uppercase(), path));
+                    operation.clone()
+                }
+            };
+            path_item.insert(method.clone(), merged);
+        }
+    }
+
+    paths
+}
+
+/// `is_name` tells that `key` is a name chosen by the API, such as a
+/// property called `description`, rather than a keyword.
+fn merge_value(key: &str, is_name: bool, existing: &Value, base: Option<&Value>, new: &Value, report: &mut UpdateReport) -> Value {
+    if existing == new {
+        return existing.clone();
+    }
+
+    let keyword_edit = !is_name && is_hand_edited_key(key);
+    if !keyword_edit {
+        match (existing, new) {
+            (Value::Object(current), Value::Object(generated)) => {
+                let names = !is_name && NAME_MAPS.contains(&key);
+                return Value::Object(merge_objects(current, base.and_then(|b| b.as_object()), generated, names, report));
+            }
+            (Value::Array(current), Value::Array(generated)) if key == "parameters" && !is_name => {
+                return Value::Array(merge_parameters(current, base.and_then(|b| b.as_array()), generated, report));
+            }
+            _ => {}
+        }
+    }
+
+    let human_edited = match base {
+        Some(base) => existing != base,
+        None => keyword_edit,
+    };
+
+    if human_edited {
+        report.kept_edits += 1;
+        existing.clone()
+    } else {
+        new.clone()
+    }
+}
+
+/// `names` tells that the keys of the objects are names rather than keywords.
+fn merge_objects(
+    existing: &Map<String, Value>,
+    base: Option<&Map<String, Value>>,
+    new: &Map<String, Value>,
+    names: bool,
+    report: &mut UpdateReport,
+) -> Map<String, Value> {
+    let mut result = Map::new();
+
+    for (key, current) in existing {
+        let base_value = base.and_then(|b| b.get(key));
+        match new.get(key) {
+            Some(generated) => {
+                result.insert(key.clone(), merge_value(key, names, current, base_value, generated, report));
+            }
+            None => {
+                // Gone from the new spec: drop it if the generator produced
+                // it and nobody touched it, keep it if a human added or
+                // edited it. Without a snapshot only hand-edited keywords
+                // can be told apart.
+                let generator_owned = match (base, base_value) {
+                    (Some(_), Some(base_value)) => base_value == current,
+                    (Some(_), None) => false,
+                    (None, _) => names || !is_hand_edited_key(key),
+                };
+                if !generator_owned {
+                    result.insert(key.clone(), current.clone());
+                }
+            }
+        }
+    }
+
+    for (key, generated) in new {
+        if existing.contains_key(key) {
+            continue;
+        }
+        // Present last time but deleted by hand: respect the deletion
+        if base.map(|b| b.contains_key(key)).unwrap_or(false) {
+            continue;
+        }
+        result.insert(key.clone(), generated.clone());
+    }
+
+    result
+}
+
+fn parameter_key(parameter: &Value) -> (Option<&Value>, Option<&Value>) {
+    (parameter.get("name"), parameter.get("in"))
+}
+
+/// Parameters are a list, but they are identified by `name` + `in`, so they
+/// are merged one by one like object entries.
+fn merge_parameters(
+    existing: &[Value],
+    base: Option<&Vec<Value>>,
+    new: &[Value],
+    report: &mut UpdateReport,
+) -> Vec<Value> {
+    let find = |list: &[Value], parameter: &Value| -> Option<Value> {
+        list.iter().find(|p| parameter_key(p) == parameter_key(parameter)).cloned()
+    };
+    let base = base.map(|b| b.as_slice()).unwrap_or(&[]);
+    let mut result = Vec::new();
+
+    for current in existing {
+        let base_parameter = find(base, current);
+        match find(new, current) {
+            Some(generated) => result.push(merge_value("parameter", false, current, base_parameter.as_ref(), &generated, report)),
+            None => {
+                if base_parameter.as_ref() != Some(current) {
+                    result.push(current.clone());
+                }
+            }
+        }
+    }
+
+    for generated in new {
+        if find(existing, generated).is_none() && find(base, generated).is_none() {
+            result.push(generated.clone());
+        }
+    }
+
+    result
+}
\ No newline at end of file
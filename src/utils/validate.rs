@@ -0,0 +1,383 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::OnceLock;
+use jsonschema::error::ValidationErrorKind;
+use serde_json::{Map, Value};
+
+use crate::models::openapi::HTTP_METHODS;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A problem found in a document, located by JSON pointer.
+#[derive(Debug, Clone)]
+pub struct Issue {
+    pub severity: Severity,
+    pub pointer: String,
+    pub message: String,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        let pointer = if self.pointer.is_empty() { "/" } else { &self.pointer };
+        write!(f, "{}: {}: {}", level, pointer, self.message)
+    }
+}
+
+/// Escapes a key for use as a JSON pointer segment (RFC 6901).
+pub fn escape_pointer(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Version {
+    V30,
+    V31,
+}
+
+// The official schemas, from https://spec.openapis.org/oas/3.0/schema/2021-09-28
+// and https://spec.openapis.org/oas/3.1/schema/2022-10-07
+const OPENAPI_30_SCHEMA: &str = include_str!("schemas/openapi-3.0.schema.json");
+const OPENAPI_31_SCHEMA: &str = include_str!("schemas/openapi-3.1.schema.json");
+
+// Instances longer than this are left out of structural error messages
+const MAX_QUOTED_LENGTH: usize = 80;
+
+/// The JSON Schema validator for documents of one OpenAPI version, built
+/// once per run.
+fn structure_validator(version: Version) -> &'static jsonschema::Validator {
+    static V30: OnceLock<jsonschema::Validator> = OnceLock::new();
+    static V31: OnceLock<jsonschema::Validator> = OnceLock::new();
+    let (cell, source) = match version {
+        Version::V30 => (&V30, OPENAPI_30_SCHEMA),
+        Version::V31 => (&V31, OPENAPI_31_SCHEMA),
+    };
+    cell.get_or_init(|| {
+        let schema: Value = serde_json::from_str(source).expect("vendored OpenAPI schema is valid JSON");
+        let compiled = match version {
+            Version::V30 => jsonschema::draft4::new(&schema),
+            Version::V31 => jsonschema::draft202012::new(&schema),
+        };
+        compiled.expect("vendored OpenAPI schema compiles")
+    })
+}
+
+/// Structural errors: where the document doesn't match the official JSON
+/// Schema of its OpenAPI version (missing or unknown fields, wrong types,
+/// invalid values).
+fn structure_issues(document: &Value, version: Version) -> Vec<Issue> {
+    // (pointer, message, unexpected names for an unevaluated-properties error)
+    let errors: Vec<(String, String, Option<Vec<String>>)> = structure_validator(version).iter_errors(document)
+        .map(|error| {
+            let mut message = error.to_string();
+            // Messages quote the failing value, which may be a whole operation
+            let quoted = error.instance.to_string();
+            if quoted.len() > MAX_QUOTED_LENGTH {
+                message = message.replacen(&quoted, "value", 1);
+            }
+            let unexpected = match &error.kind {
+                ValidationErrorKind::UnevaluatedProperties { unexpected } => Some(unexpected.clone()),
+                _ => None,
+            };
+            (error.instance_path.to_string(), message, unexpected)
+        })
+        .collect();
+
+    // In 3.1 an invalid field also leaves every object above it with an
+    // "unevaluated" property leading to it; only the innermost error is useful
+    let has_error_at = |pointer: &str| {
+        errors.iter().any(|(other, _, _)| other == pointer || other.starts_with(&format!("{}/", pointer)))
+    };
+    errors.iter()
+        .filter_map(|(pointer, message, unexpected)| {
+            let message = match unexpected {
+                None => message.clone(),
+                Some(names) => {
+                    let names: Vec<String> = names.iter()
+                        .filter(|name| !has_error_at(&format!("{}/{}", pointer, escape_pointer(name))))
+                        .map(|name| format!("'{}'", name))
+                        .collect();
+                    match names.len() {
+                        0 => return None,
+                        1 => format!("Unevaluated properties are not allowed ({} was unexpected)", names[0]),
+                        _ => format!("Unevaluated properties are not allowed ({} were unexpected)", names.join(", ")),
+                    }
+                }
+            };
+            Some(Issue {
+                severity: Severity::Error,
+                pointer: pointer.clone(),
+                message,
+            })
+        })
+        .collect()
+}
+
+/// The rules a JSON Schema can't express. Structure is checked separately,
+/// so this pass skips values of the wrong shape without reporting them.
+struct Validator {
+    version: Version,
+    issues: Vec<Issue>,
+}
+
+impl Validator {
+    fn error(&mut self, pointer: &str, message: impl Into<String>) {
+        self.issues.push(Issue {
+            severity: Severity::Error,
+            pointer: pointer.to_string(),
+            message: message.into(),
+        });
+    }
+
+    fn warning(&mut self, pointer: &str, message: impl Into<String>) {
+        self.issues.push(Issue {
+            severity: Severity::Warning,
+            pointer: pointer.to_string(),
+            message: message.into(),
+        });
+    }
+
+    fn document(&mut self, root: &Map<String, Value>) {
+        if let Some(paths) = root.get("paths").and_then(|p| p.as_object()) {
+            self.paths(paths);
+        }
+
+        let schemas = root.get("components")
+            .and_then(|c| c.get("schemas"))
+            .and_then(|s| s.as_object());
+        for (name, schema) in schemas.into_iter().flatten() {
+            self.schema(schema, &format!("/components/schemas/{}", escape_pointer(name)));
+        }
+
+        let mut seen = HashSet::new();
+        let tags = root.get("tags").and_then(|t| t.as_array());
+        for (i, tag) in tags.into_iter().flatten().enumerate() {
+            if let Some(name) = tag.get("name").and_then(|n| n.as_str()) {
+                if !seen.insert(name.to_string()) {
+                    self.error(&format!("/tags/{}", i), format!("duplicate tag '{}'", name));
+                }
+            }
+        }
+    }
+
+    fn paths(&mut self, paths: &Map<String, Value>) {
+        let mut operation_ids: HashMap<String, String> = HashMap::new();
+        let mut templates: HashMap<String, String> = HashMap::new();
+
+        for (path, item) in paths {
+            if path.starts_with("x-") {
+                continue;
+            }
+            let pointer = format!("/paths/{}", escape_pointer(path));
+
+            if path.contains("{{") || path.contains("}}") {
+                self.error(&pointer, format!("path '{}' contains an unresolved Postman variable", path));
+            }
+
+            // `/users/{id}` and `/users/{userId}` describe the same URLs
+            let shape = template_shape(path);
+            if let Some(other) = templates.insert(shape, path.clone()) {
+                self.error(&pointer, format!("path '{}' is equivalent to '{}'", path, other));
+            }
+
+            let Some(item) = item.as_object() else { continue };
+            let path_parameters = item.get("parameters").cloned().unwrap_or(Value::Array(Vec::new()));
+            self.parameters(&path_parameters, &format!("{}/parameters", pointer));
+
+            for (method, operation) in item {
+                if HTTP_METHODS.contains(&method.as_str()) {
+                    let operation_pointer = format!("{}/{}", pointer, method);
+                    self.operation(path, operation, &path_parameters, &operation_pointer, &mut operation_ids);
+                }
+            }
+        }
+    }
+
+    fn operation(
+        &mut self,
+        path: &str,
+        operation: &Value,
+        path_parameters: &Value,
+        pointer: &str,
+        operation_ids: &mut HashMap<String, String>,
+    ) {
+        let Some(operation) = operation.as_object() else { return };
+
+        if let Some(id) = operation.get("operationId").and_then(|id| id.as_str()) {
+            if let Some(other) = operation_ids.insert(id.to_string(), pointer.to_string()) {
+                self.error(pointer, format!("operationId '{}' is already used at {}", id, other));
+            }
+        }
+
+        let parameters = operation.get("parameters").cloned().unwrap_or(Value::Array(Vec::new()));
+        self.parameters(&parameters, &format!("{}/parameters", pointer));
+        self.path_templating(path, path_parameters, &parameters, pointer);
+
+        for (i, parameter) in parameters.as_array().into_iter().flatten().enumerate() {
+            if let Some(schema) = parameter.get("schema") {
+                self.schema(schema, &format!("{}/parameters/{}/schema", pointer, i));
+            }
+        }
+        if let Some(content) = operation.get("requestBody").and_then(|b| b.get("content")) {
+            self.content(content, &format!("{}/requestBody/content", pointer));
+        }
+        let responses = operation.get("responses").and_then(|r| r.as_object());
+        for (status, response) in responses.into_iter().flatten() {
+            if let Some(content) = response.get("content") {
+                self.content(content, &format!("{}/responses/{}/content", pointer, escape_pointer(status)));
+            }
+        }
+    }
+
+    /// Parameters are identified by name and location; header names are
+    /// case-insensitive.
+    fn parameters(&mut self, parameters: &Value, pointer: &str) {
+        let mut seen = HashSet::new();
+        for (i, parameter) in parameters.as_array().into_iter().flatten().enumerate() {
+            let name = parameter.get("name").and_then(|n| n.as_str());
+            let location = parameter.get("in").and_then(|l| l.as_str());
+            if let (Some(name), Some(location)) = (name, location) {
+                let key = if location == "header" { name.to_lowercase() } else { name.to_string() };
+                if !seen.insert((key, location.to_string())) {
+                    self.error(&format!("{}/{}", pointer, i), format!("duplicate parameter '{}' in {}", name, location));
+                }
+            }
+        }
+    }
+
+    /// Every `{name}` in the path needs a path parameter, and every path
+    /// parameter needs a `{name}` in the path.
+    fn path_templating(&mut self, path: &str, path_parameters: &Value, parameters: &Value, pointer: &str) {
+        let template: HashSet<String> = template_names(path).into_iter().collect();
+        let declared: HashSet<String> = path_parameters.as_array().into_iter().flatten()
+            .chain(parameters.as_array().into_iter().flatten())
+            .filter(|p| p.get("in").and_then(|l| l.as_str()) == Some("path"))
+            .filter_map(|p| p.get("name").and_then(|n| n.as_str()))
+            .map(|n| n.to_string())
+            .collect();
+
+        let mut missing: Vec<&String> = template.difference(&declared).collect();
+        missing.sort();
+        for name in missing {
+            self.error(pointer, format!("path template '{{{}}}' has no matching path parameter", name));
+        }
+
+        let mut extra: Vec<&String> = declared.difference(&template).collect();
+        extra.sort();
+        for name in extra {
+            self.error(pointer, format!("path parameter '{}' does not appear in the path", name));
+        }
+    }
+
+    fn content(&mut self, content: &Value, pointer: &str) {
+        for (media_type, media) in content.as_object().into_iter().flatten() {
+            if let Some(schema) = media.get("schema") {
+                self.schema(schema, &format!("{}/{}/schema", pointer, escape_pointer(media_type)));
+            }
+        }
+    }
+
+    fn schema(&mut self, schema: &Value, pointer: &str) {
+        let Some(schema) = schema.as_object() else { return };
+        if schema.contains_key("$ref") {
+            return;
+        }
+
+        let is_array = match schema.get("type") {
+            Some(Value::String(t)) => t == "array",
+            _ => false,
+        };
+        if self.version == Version::V30 && is_array && !schema.contains_key("items") {
+            self.error(pointer, "array schemas must define 'items'");
+        }
+
+        let required = schema.get("required").and_then(|r| r.as_array());
+        if let (Some(required), Some(properties)) = (required, schema.get("properties").and_then(|p| p.as_object())) {
+            for name in required.iter().filter_map(|n| n.as_str()) {
+                if !properties.contains_key(name) {
+                    self.warning(&format!("{}/required", pointer), format!("required property '{}' is not defined", name));
+                }
+            }
+        }
+
+        let properties = schema.get("properties").and_then(|p| p.as_object());
+        for (name, property) in properties.into_iter().flatten() {
+            self.schema(property, &format!("{}/properties/{}", pointer, escape_pointer(name)));
+        }
+
+        if let Some(items) = schema.get("items") {
+            self.schema(items, &format!("{}/items", pointer));
+        }
+
+        for keyword in ["allOf", "oneOf", "anyOf"] {
+            for (i, sub) in schema.get(keyword).and_then(|l| l.as_array()).into_iter().flatten().enumerate() {
+                self.schema(sub, &format!("{}/{}/{}", pointer, keyword, i));
+            }
+        }
+    }
+}
+
+/// Names of the `{name}` templates in a path, ignoring Postman `{{vars}}`.
+pub fn template_names(path: &str) -> Vec<String> {
+    path.split('/')
+        .filter(|segment| !segment.contains("{{"))
+        .flat_map(|segment| {
+            segment.split('{')
+                .skip(1)
+                .filter_map(|part| part.split_once('}').map(|(name, _)| name.to_string()))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn template_shape(path: &str) -> String {
+    path.split('/')
+        .map(|segment| if segment.starts_with('{') && segment.ends_with('}') { "{}" } else { segment })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Checks a document against the official JSON Schema of its declared
+/// OpenAPI version (3.0.x or 3.1.x), then against the semantic rules the
+/// schema can't express: path templating, unique operationIds, parameters
+/// and tags, and `items` on 3.0 array schemas.
+pub fn validate_document(document: &Value) -> Vec<Issue> {
+    let version = match document.get("openapi").and_then(|v| v.as_str()) {
+        Some(version) if version.starts_with("3.0.") => Version::V30,
+        Some(version) if version.starts_with("3.1.") => Version::V31,
+        Some(version) => {
+            return vec![Issue {
+                severity: Severity::Error,
+                pointer: "/openapi".to_string(),
+                message: format!("unsupported OpenAPI version '{}'", version),
+            }];
+        }
+        None => {
+            return vec![Issue {
+                severity: Severity::Error,
+                pointer: String::new(),
+                message: "missing required field 'openapi'".to_string(),
+            }];
+        }
+    };
+
+    let mut validator = Validator {
+        version,
+        issues: structure_issues(document, version),
+    };
+    if let Some(root) = document.as_object() {
+        validator.document(root);
+    }
+    validator.issues
+}
+
+pub fn has_errors(issues: &[Issue]) -> bool {
+    issues.iter().any(|issue| issue.severity == Severity::Error)
+}
\ No newline at end of file
@@ -0,0 +1,408 @@
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::converters::postman::{convert_postman_to_openapi, path_template};
+use crate::models::openapi::OpenAPISpec;
+use crate::models::postman::{Item, PostmanCollection};
+use crate::utils::contract::{case_request, encode, env_name, CaseRequest};
+use crate::utils::examples::{allows_null, schema_types};
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How a live response differs from the converted spec.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DriftKind {
+    /// The status code isn't one of the operation's responses
+    UndocumentedStatus,
+    /// The body has a property the schema doesn't list
+    NewField,
+    /// A required property is missing from the body
+    MissingField,
+    /// A value has a different type than the schema says
+    TypeChanged,
+    /// The schema expects JSON but the body isn't
+    InvalidBody,
+}
+
+impl DriftKind {
+    fn label(self) -> &'static str {
+        match self {
+            DriftKind::UndocumentedStatus => "undocumented status",
+            DriftKind::NewField => "new field",
+            DriftKind::MissingField => "missing field",
+            DriftKind::TypeChanged => "type changed",
+            DriftKind::InvalidBody => "invalid body",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Drift {
+    pub kind: DriftKind,
+    /// Where in the body, e.g. `$.address.zip` or `$.items[].id`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub at: Option<String>,
+    pub message: String,
+}
+
+/// The replay of one request.
+#[derive(Debug, Serialize)]
+pub struct Outcome {
+    pub name: String,
+    /// `METHOD /path/{template}` of the operation
+    pub operation: String,
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    /// Why the request couldn't be sent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub drift: Vec<Drift>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct VerifyReport {
+    pub outcomes: Vec<Outcome>,
+}
+
+fn value_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn matches_type(value: &Value, schema_type: &str) -> bool {
+    match schema_type {
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "object" | "array" | "string" | "boolean" | "null" => value_type(value) == schema_type,
+        _ => true,
+    }
+}
+
+/// Collects how `value` departs from `schema`. Array items share one
+/// location (`$.items[]`), so a drift shared by every item is reported once.
+fn compare(value: &Value, schema: &Value, at: &str, drift: &mut Vec<Drift>) {
+    if value.is_null() && allows_null(schema) {
+        return;
+    }
+    let types = schema_types(schema);
+    if !types.is_empty() && !types.iter().any(|t| matches_type(value, t)) {
+        drift.push(Drift {
+            kind: DriftKind::TypeChanged,
+            at: Some(at.to_string()),
+            message: format!("expected {}, found {}", types.join(" or "), value_type(value)),
+        });
+        return;
+    }
+    match value {
+        Value::Object(object) => {
+            let required: Vec<&str> = schema.get("required")
+                .and_then(|r| r.as_array())
+                .map(|r| r.iter().filter_map(|n| n.as_str()).collect())
+                .unwrap_or_default();
+            for name in &required {
+                if !object.contains_key(*name) {
+                    drift.push(Drift {
+                        kind: DriftKind::MissingField,
+                        at: Some(format!("{}.{}", at, name)),
+                        message: "required property is missing".to_string(),
+                    });
+                }
+            }
+            // An object inferred without properties says nothing about its fields
+            let Some(properties) = schema.get("properties").and_then(|p| p.as_object()).filter(|p| !p.is_empty()) else {
+                return;
+            };
+            for (name, child) in object {
+                let child_at = format!("{}.{}", at, name);
+                match properties.get(name) {
+                    // Saved nulls can't tell the type, so optional fields may be null
+                    Some(_) if child.is_null() && !required.contains(&name.as_str()) => {}
+                    Some(property) => compare(child, property, &child_at, drift),
+                    None => drift.push(Drift {
+                        kind: DriftKind::NewField,
+                        at: Some(child_at),
+                        message: format!("not in the schema ({})", value_type(child)),
+                    }),
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for item in items {
+                    compare(item, item_schema, &format!("{}[]", at), drift);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The JSON body schema converted for a response, or `None` when the
+/// response has no JSON body.
+fn json_schema(spec: &OpenAPISpec, template: &str, method: &str, status: &str) -> Option<Value> {
+    let response = spec.paths.get(template)?.get(method)?.responses.get(status)?;
+    response.content.iter()
+        .find(|(content_type, _)| content_type.contains("json"))
+        .and_then(|(_, content)| serde_json::to_value(&content.schema).ok())
+}
+
+/// Replays requests against a server and compares the answers with the
+/// spec converted from the same collection.
+pub struct Verifier {
+    spec: OpenAPISpec,
+    base_url: String,
+    variables: HashMap<String, String>,
+    agent: ureq::Agent,
+}
+
+impl Verifier {
+    /// `variables` fill in `{{name}}` variables and `:name` path segments
+    /// that have no saved value.
+    pub fn new(collection: &PostmanCollection, base_url: &str, variables: HashMap<String, String>) -> Self {
+        Verifier {
+            spec: convert_postman_to_openapi(collection),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            variables,
+            agent: ureq::AgentBuilder::new().timeout(TIMEOUT).redirects(0).build(),
+        }
+    }
+
+    /// The value of a variable: the one given to `new`, otherwise
+    /// `CONTRACT_<NAME>` from the environment, like the generated contract tests.
+    fn variable(&self, name: &str) -> Result<String, String> {
+        if let Some(value) = self.variables.get(name) {
+            return Ok(value.clone());
+        }
+        let env = env_name(name);
+        std::env::var(&env).map_err(|_| format!("no value for '{}'; pass --var {}=VALUE or set {}", name, name, env))
+    }
+
+    /// Replaces every `{{name}}` in `text`, percent-encoding the values
+    /// when `text` is part of the URL.
+    fn substitute(&self, text: &str, in_url: bool) -> Result<String, String> {
+        let mut out = String::new();
+        let mut rest = text;
+        while let Some(start) = rest.find("{{") {
+            let Some(length) = rest[start..].find("}}") else { break };
+            let value = self.variable(rest[start + 2..start + length].trim())?;
+            out.push_str(&rest[..start]);
+            out.push_str(&if in_url { encode(&value) } else { value });
+            rest = &rest[start + length + 2..];
+        }
+        out.push_str(rest);
+        Ok(out)
+    }
+
+    /// Fills in the variables of a request so nothing is sent literally.
+    fn fill(&self, request: &mut CaseRequest) -> Result<(), String> {
+        let (path, query) = match request.path.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (request.path.as_str(), None),
+        };
+        let segments = path.split('/')
+            .map(|segment| match segment.strip_prefix(':') {
+                Some(name) if !name.is_empty() => self.variable(name).map(|value| encode(&value)),
+                _ => self.substitute(segment, true),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut filled = segments.join("/");
+        if let Some(query) = query {
+            filled.push('?');
+            filled.push_str(&self.substitute(query, true)?);
+        }
+        request.path = filled;
+        for (_, value) in request.headers.iter_mut() {
+            *value = self.substitute(value, false)?;
+        }
+        if let Some(body) = request.body.as_mut() {
+            *body = self.substitute(body, false)?;
+        }
+        Ok(())
+    }
+
+    fn send(&self, request: &CaseRequest) -> Result<(u16, String), String> {
+        let url = format!("{}{}", self.base_url, request.path);
+        let mut call = self.agent.request(&request.method, &url);
+        for (name, value) in &request.headers {
+            call = call.set(name, value);
+        }
+        let result = match &request.body {
+            Some(body) => call.send_string(body),
+            None => call.call(),
+        };
+        match result {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => {
+                let status = response.status();
+                response.into_string().map(|body| (status, body)).map_err(|e| e.to_string())
+            }
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Sends one item's request and checks the response.
+    pub fn check(&self, item: &Item) -> Option<Outcome> {
+        let request = item.request.as_ref()?;
+        let method = request.method.as_deref().unwrap_or("GET").to_uppercase();
+        let template = request.url.as_ref().map(path_template).unwrap_or_else(|| "/".to_string());
+        let mut sent = case_request(request, &method);
+        let filled = self.fill(&mut sent);
+        let mut outcome = Outcome {
+            name: item.name.clone().unwrap_or_default(),
+            operation: format!("{} {}", method, template),
+            url: format!("{}{}", self.base_url, sent.path),
+            status: None,
+            error: None,
+            drift: Vec::new(),
+        };
+        if let Err(e) = filled {
+            outcome.error = Some(e);
+            return Some(outcome);
+        }
+
+        let (status, body) = match self.send(&sent) {
+            Ok(reply) => reply,
+            Err(e) => {
+                outcome.error = Some(e);
+                return Some(outcome);
+            }
+        };
+        outcome.status = Some(status);
+
+        let method = method.to_lowercase();
+        let documented: Vec<&String> = self.spec.paths.get(&template)
+            .and_then(|operations| operations.get(&method))
+            .map(|operation| operation.responses.keys().collect())
+            .unwrap_or_default();
+        let status_key = status.to_string();
+        if !documented.contains(&&status_key) {
+            let documented: Vec<&str> = documented.iter().map(|s| s.as_str()).collect();
+            outcome.drift.push(Drift {
+                kind: DriftKind::UndocumentedStatus,
+                at: None,
+                message: format!("{} is not documented (documented: {})", status, documented.join(", ")),
+            });
+            return Some(outcome);
+        }
+
+        let Some(schema) = json_schema(&self.spec, &template, &method, &status_key) else {
+            return Some(outcome);
+        };
+        match serde_json::from_str::<Value>(&body) {
+            Ok(value) => {
+                let mut drift = Vec::new();
+                compare(&value, &schema, "$", &mut drift);
+                let mut seen = HashSet::new();
+                drift.retain(|d| seen.insert((d.kind, d.at.clone(), d.message.clone())));
+                outcome.drift = drift;
+            }
+            Err(e) => outcome.drift.push(Drift {
+                kind: DriftKind::InvalidBody,
+                at: None,
+                message: format!("expected JSON: {}", e),
+            }),
+        }
+        Some(outcome)
+    }
+
+    fn walk(&self, items: &[Item], report: &mut VerifyReport, on_outcome: &impl Fn(&Outcome)) {
+        for item in items {
+            if let Some(children) = &item.item {
+                self.walk(children, report, on_outcome);
+            } else if let Some(outcome) = self.check(item) {
+                on_outcome(&outcome);
+                report.outcomes.push(outcome);
+            }
+        }
+    }
+
+    /// Replays every request of the collection in order, calling
+    /// `on_outcome` as each one finishes.
+    pub fn run(&self, collection: &PostmanCollection, on_outcome: impl Fn(&Outcome)) -> VerifyReport {
+        let mut report = VerifyReport::default();
+        self.walk(collection.item.as_deref().unwrap_or_default(), &mut report, &on_outcome);
+        report
+    }
+}
+
+impl VerifyReport {
+    pub fn drift_count(&self) -> usize {
+        self.outcomes.iter().map(|o| o.drift.len()).sum()
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.outcomes.iter().filter(|o| o.error.is_some()).count()
+    }
+
+    /// Whether every request was answered as the spec says.
+    pub fn passed(&self) -> bool {
+        self.drift_count() == 0 && self.error_count() == 0
+    }
+
+    /// One line per request, followed by its drift.
+    pub fn outcome_text(outcome: &Outcome) -> String {
+        let result = match (&outcome.error, outcome.status) {
+            (Some(e), _) => format!("error: {}", e),
+            (None, Some(status)) if outcome.drift.is_empty() => format!("{} ok", status),
+            (None, Some(status)) => format!("{} drift", status),
+            (None, None) => "not sent".to_string(),
+        };
+        let mut out = format!("{} ({}): {}\n", outcome.operation, outcome.name, result);
+        for drift in &outcome.drift {
+            match &drift.at {
+                Some(at) => out.push_str(&format!("  {} {}: {}\n", drift.kind.label(), at, drift.message)),
+                None => out.push_str(&format!("  {}: {}\n", drift.kind.label(), drift.message)),
+            }
+        }
+        out
+    }
+
+    pub fn summary(&self) -> String {
+        let drifted = self.outcomes.iter().filter(|o| !o.drift.is_empty()).count();
+        format!(
+            "\n{} request(s): {} matched, {} with drift ({} finding(s)), {} failed\n",
+            self.outcomes.len(),
+            self.outcomes.len() - drifted - self.error_count(),
+            drifted,
+            self.drift_count(),
+            self.error_count()
+        )
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from("# API Verification\n");
+        out.push_str(&self.summary());
+        for outcome in self.outcomes.iter().filter(|o| !o.drift.is_empty() || o.error.is_some()) {
+            out.push_str(&format!("\n## `{}` ({})\n\n", outcome.operation, outcome.name));
+            if let Some(e) = &outcome.error {
+                out.push_str(&format!("- request failed: {}\n", e));
+            }
+            for drift in &outcome.drift {
+                match &drift.at {
+                    Some(at) => out.push_str(&format!("- {} `{}`: {}\n", drift.kind.label(), at, drift.message)),
+                    None => out.push_str(&format!("- {}: {}\n", drift.kind.label(), drift.message)),
+                }
+            }
+        }
+        out
+    }
+
+    pub fn to_json(&self) -> String {
+        let report = serde_json::json!({
+            "requests": self.outcomes.len(),
+            "drift": self.drift_count(),
+            "errors": self.error_count(),
+            "outcomes": self.outcomes,
+        });
+        serde_json::to_string_pretty(&report).unwrap_or_default()
+    }
+}
@@ -0,0 +1,105 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// How often watched files are checked.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// How long files must stay unchanged before a batch of changes is reported,
+/// so an editor's save-in-several-writes triggers one rebuild.
+pub const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Files that appeared, changed or disappeared since the last poll.
+#[derive(Debug, Default)]
+pub struct Changes {
+    pub modified: BTreeSet<PathBuf>,
+    pub removed: BTreeSet<PathBuf>,
+}
+
+impl Changes {
+    pub fn is_empty(&self) -> bool {
+        self.modified.is_empty() && self.removed.is_empty()
+    }
+
+    fn extend(&mut self, later: Changes) {
+        for path in later.modified {
+            self.removed.remove(&path);
+            self.modified.insert(path);
+        }
+        for path in later.removed {
+            self.modified.remove(&path);
+            self.removed.insert(path);
+        }
+    }
+}
+
+/// Modification time of a file, or of the newest file inside a directory
+/// (Bruno collections are folders).
+pub fn last_modified(path: &Path) -> Option<SystemTime> {
+    let metadata = fs::metadata(path).ok()?;
+    if !metadata.is_dir() {
+        return metadata.modified().ok();
+    }
+    fs::read_dir(path).ok()?
+        .flatten()
+        .filter_map(|entry| last_modified(&entry.path()))
+        .chain(metadata.modified().ok())
+        .max()
+}
+
+/// Detects changes by polling modification times, which works the same on
+/// every platform and file system.
+pub struct Watcher {
+    seen: HashMap<PathBuf, SystemTime>,
+}
+
+impl Watcher {
+    /// Starts watching `paths` as they are now.
+    pub fn new(paths: &[PathBuf]) -> Self {
+        let seen = paths.iter()
+            .filter_map(|path| last_modified(path).map(|time| (path.clone(), time)))
+            .collect();
+        Watcher { seen }
+    }
+
+    /// Compares `paths` with the previous poll. Paths no longer listed
+    /// count as removed.
+    pub fn poll(&mut self, paths: &[PathBuf]) -> Changes {
+        let mut changes = Changes::default();
+        let mut current = HashMap::new();
+        for path in paths {
+            let Some(time) = last_modified(path) else { continue };
+            if self.seen.get(path) != Some(&time) {
+                changes.modified.insert(path.clone());
+            }
+            current.insert(path.clone(), time);
+        }
+        for path in self.seen.keys() {
+            if !current.contains_key(path) {
+                changes.removed.insert(path.clone());
+            }
+        }
+        self.seen = current;
+        changes
+    }
+
+    /// Blocks until something changes, then until the files have been quiet
+    /// for `DEBOUNCE`, and returns everything that changed meanwhile.
+    /// `list` is called on every poll so new files are picked up.
+    pub fn wait(&mut self, list: impl Fn() -> Vec<PathBuf>) -> Changes {
+        let mut changes = Changes::default();
+        while changes.is_empty() {
+            thread::sleep(POLL_INTERVAL);
+            changes = self.poll(&list());
+        }
+        loop {
+            thread::sleep(DEBOUNCE);
+            let later = self.poll(&list());
+            if later.is_empty() {
+                return changes;
+            }
+            changes.extend(later);
+        }
+    }
+}